@@ -3,8 +3,6 @@
 //! The [`Dex`] type contains listings of various resources from PokeAPI, which
 //! can be processed to display to a user.
 
-use std::sync::atomic::AtomicBool;
-use std::sync::atomic::Ordering;
 use std::sync::mpsc;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -24,9 +22,21 @@ use pkmn::model::Species;
 use pkmn::model::Type;
 use pkmn::Api;
 
+use crate::download::Download;
+use crate::download::Progress;
+
+/// The page size [`Resources::all()`] walks listings with.
+const LISTING_PAGE_SIZE: usize = 64;
+
+/// The number of resources [`Resources::all()`] loads at a time.
+const LISTING_CONCURRENCY: usize = 8;
+
+/// Every resource of some type, or `None` if the listing failed to load.
+type Listed<T> = Option<Arc<[Arc<T>]>>;
+
 pub struct Resources<T> {
   api: Arc<Api>,
-  names: Arc<(AtomicBool, Mutex<Option<Arc<[Arc<T>]>>>)>,
+  all: Mutex<Download<Listed<T>, api::Error>>,
   table: Arc<DashMap<String, Option<Arc<T>>>>,
   error_sink: mpsc::Sender<api::Error>,
 }
@@ -35,7 +45,7 @@ impl<T: Endpoint> Resources<T> {
   pub fn new(api: Arc<Api>, error_sink: mpsc::Sender<api::Error>) -> Self {
     Self {
       api,
-      names: Default::default(),
+      all: Mutex::new(Download::new()),
       table: Default::default(),
       error_sink,
     }
@@ -72,55 +82,55 @@ impl<T: Endpoint> Resources<T> {
     self.get(name.to_str())
   }
 
-  pub fn all(&self) -> Option<Arc<[Arc<T>]>> {
-    // Don't do anything if there's a download thread running.
-    if self.names.0.load(Ordering::SeqCst) {
-      return None;
-    }
-
-    // NOTE: We can only fail to take the lock if the download thread is
-    // currently uploading, so we don't need to bother spawning another one.
-    if let Some(names) = &*self.names.1.try_lock().ok()? {
-      return Some(Arc::clone(names));
-    }
-
-    // Lock the pending bit.
-    self.names.0.store(true, Ordering::SeqCst);
+  /// Returns every resource of type `T`, or how far along loading them is.
+  ///
+  /// The first call starts loading them in the background; errors loading
+  /// individual resources are sent to the error sink.
+  pub fn all(&self) -> Result<Arc<[Arc<T>]>, Progress<api::Error>> {
+    let mut all = self.all.lock().unwrap();
+    all.start({
+      let api = Arc::clone(&self.api);
+      let error_sink = self.error_sink.clone();
+      move |notifier| {
+        notifier.send_message(format!("Loading {}...", T::NAME));
+        let mut values = Vec::new();
+        let mut listing = api.listing_of::<T>(LISTING_PAGE_SIZE);
+        loop {
+          let results = match listing.advance() {
+            Ok(Some(results)) => results,
+            Ok(None) => return Some(values.into_boxed_slice().into()),
+            Err(e) => {
+              let _ = error_sink.send(e);
+              return None;
+            }
+          };
 
-    let api = Arc::clone(&self.api);
-    let slot = Arc::clone(&self.names);
-    let error_sink = self.error_sink.clone();
-    let mut values = Vec::new();
-    thread::spawn(move || {
-      let mut listing = api.listing_of::<T>(64);
-      loop {
-        match listing.advance() {
-          Ok(Some(results)) => {
-            for result in &*results {
-              match result.load(&api) {
-                Ok(x) => values.push(x),
-                Err(e) => {
-                  let _ = error_sink.send(e);
-                }
+          for result in results.load_all(&api, LISTING_CONCURRENCY, &notifier) {
+            match result {
+              Ok(x) => values.push(x),
+              Err(e) => {
+                let _ = error_sink.send(e);
               }
             }
           }
-          Ok(None) => {
-            *slot.1.lock().unwrap() = Some(values.into_boxed_slice().into());
-            break;
-          }
-          Err(e) => {
-            let _ = error_sink.send(e);
-            break;
-          }
         }
       }
-
-      // Release the pending bit.
-      slot.0.store(false, Ordering::SeqCst);
     });
 
-    None
+    match all.try_finish() {
+      Ok(Some(values)) => return Ok(Arc::clone(values)),
+      Ok(None) => {}
+      Err(progress) => return Err(progress),
+    }
+
+    // The listing itself failed to load, so start over on the next call.
+    *all = Download::new();
+    Err(Progress {
+      message: None,
+      errors: Vec::new(),
+      completed: 0,
+      total: 0,
+    })
   }
 }
 
@@ -11,6 +11,8 @@ use std::sync::mpsc::Sender;
 use std::sync::Arc;
 use std::thread;
 
+use pkmn::api::ReportProgress;
+
 /// A value that may need to be downloaded.
 ///
 /// `E` represents errors that may occur during the download.
@@ -83,6 +85,16 @@ impl<E> Notifier<E> {
   }
 }
 
+impl<E: Send> ReportProgress for Notifier<E> {
+  fn add_total(&self, n: usize) {
+    self.inc_total(n);
+  }
+
+  fn add_completed(&self, n: usize) {
+    self.inc_completed(n);
+  }
+}
+
 impl<T: Send + 'static, E: Send + 'static> Download<T, E> {
   /// Creates a new [`Download`].
   pub fn new() -> Self {
@@ -41,7 +41,11 @@ fn main() -> Result<(), crossterm::ErrorKind> {
 }
 
 fn real_main() -> Result<(), crossterm::ErrorKind> {
-  let api = Arc::new(Api::with_cache(Cache::new(2048)));
+  // PokéAPI data changes rarely, but it does change; revalidate weekly.
+  let cache = Cache::new(2048)
+    .expire_after(Duration::from_secs(7 * 24 * 3600))
+    .disk_budget(512 << 20);
+  let api = Arc::new(Api::with_cache(cache));
 
   let (error_sink, errors) = mpsc::channel();
   let dex = dex::Dex::new(Arc::clone(&api), error_sink);
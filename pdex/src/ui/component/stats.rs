@@ -4,6 +4,10 @@ use std::fmt::Debug;
 use std::iter;
 use std::sync::Arc;
 
+use pkmn::calc::stats::Calculator;
+use pkmn::calc::stats::Formula;
+use pkmn::calc::stats::Spread;
+use pkmn::calc::stats::MAX_TOTAL_EVS;
 use pkmn::model::species::BaseStat;
 use pkmn::model::LanguageName;
 use pkmn::model::Nature;
@@ -12,7 +16,6 @@ use pkmn::model::StatName;
 
 use crossterm::event::KeyCode;
 use crossterm::event::MouseButton;
-use crossterm::event::MouseEvent;
 use crossterm::event::MouseEventKind;
 
 use tui::layout::Rect;
@@ -108,7 +111,7 @@ impl StatsView {
 
       StatFocusType::Ev => {
         let sum: u16 = self.stats.iter().map(|s| s.ev as u16).sum();
-        let spare = 510u16
+        let spare = (MAX_TOTAL_EVS as u16)
           // Note that we need to skip the stat we're modifying, so that the old
           // value doesn't screw with the "leftovers" computation.
           .saturating_sub(
@@ -287,8 +290,8 @@ impl Component for StatsView {
 
     if self.natures.is_empty() {
       self.natures = match args.dex.natures.all() {
-        Some(natures) => natures.iter().cloned().collect(),
-        None => return,
+        Ok(natures) => natures.iter().cloned().collect(),
+        Err(_) => return,
       };
 
       self.natures.sort_by(|n1, n2| {
@@ -345,6 +348,7 @@ impl Component for StatsView {
     };
 
     let level = self.level as u32;
+    let calc = Calculator::new(Formula::Modern, self.level).nature(nature);
     let legend = vec![
       /* 0 */ Span::styled("    Base ", style),
       /* 1 */
@@ -445,46 +449,33 @@ impl Component for StatsView {
       );
       *ev_rect = Rect::new(evs_x, args.rect.y + 1 + i as u16, evs_width, 1);
 
-      // TODO: Add dedicated method on `Nature`.
-      let (nature_multiplier, multiplier_icon) = if nature
-        .increases
-        .as_ref()
-        .map(|n| n.is(variant))
-        .unwrap_or(false)
-      {
-        (1.1, "+")
-      } else if nature
-        .decreases
-        .as_ref()
-        .map(|n| n.is(variant))
-        .unwrap_or(false)
-      {
-        (0.9, "-")
+      let multiplier_icon = if nature.raises(variant) {
+        "+"
+      } else if nature.lowers(variant) {
+        "-"
       } else {
-        (1.0, " ")
+        " "
       };
 
-      // See: https://bulbapedia.bulbagarden.net/wiki/Stat#In_Generation_III_onward
-      // TODO: allow a way to compute using the Gen I/II formula.
-      *actual = if let Some(StatName::HitPoints) = base.stat.variant() {
-        if self.pokemon.name == "shedinja" {
-          // Lmao Shedinja.
-          1
-        } else {
-          ((2 * base.base_stat + iv + ev / 4) * level) / 100 + level + 10
+      // The inputs are clamped while editing, so this only fails for stats
+      // the calculator refuses to compute; show that rather than a bogus 0.
+      let spread = Spread::new(iv as u8, ev as u16);
+      let computed = match calc.stat(variant, base.base_stat, spread) {
+        Ok(value) => {
+          *actual = value;
+          format!("-> {}{:3}", multiplier_icon, value)
+        }
+        Err(_) => {
+          *actual = 0;
+          format!("-> {}???", multiplier_icon)
         }
-      } else {
-        let pre_nature = ((2 * base.base_stat + iv + ev / 4) * level) / 100 + 5;
-        (pre_nature as f64 * nature_multiplier) as u32
       };
-
-      let computed =
-        Span::styled(format!("-> {}{:3}", multiplier_icon, *actual), style);
+      let computed = Span::styled(computed, style);
 
       /// The final values of stats *rarely* go over 500. Note that we adjust
       /// HP to not incorporate the `level + 10` component for this purpose.
       let actual = if variant == StatName::HitPoints {
-        *actual - 5 - level
+        actual.saturating_sub(5 + level)
       } else {
         *actual
       };
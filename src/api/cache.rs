@@ -1,17 +1,45 @@
 //! Caching utilities.
 
 use std::any::Any;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fs;
+use std::fs::File;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Write;
 use std::mem::MaybeUninit;
+use std::path::Path;
 use std::path::PathBuf;
+use std::process;
 use std::ptr;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::Condvar;
 use std::sync::Mutex;
+use std::time::Duration;
+use std::time::SystemTime;
 
+#[cfg(feature = "gzip")]
+use flate2::read::GzDecoder;
+#[cfg(feature = "gzip")]
+use flate2::write::GzEncoder;
+#[cfg(feature = "async")]
+use futures::channel::oneshot;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::api::transport::Conditional;
+use crate::api::transport::Freshness;
 use crate::api::Error;
+use crate::api::ErrorKind;
 
 #[cfg(doc)]
 use crate::api::Api;
@@ -23,20 +51,466 @@ use crate::api::Api;
 ///
 /// When looking things up in the cache, chache misses go to disk before
 /// performing actual computation.
+///
+/// Entries record when they were fetched, and go stale once they are older than
+/// their time-to-live: either the one set with [`Cache::expire_after()`], or
+/// else the one the server specified (via `Cache-Control: max-age`). Entries
+/// with neither never go stale. Stale entries are revalidated with the server
+/// (using their `ETag` or `Last-Modified` date, when available) before being
+/// used again; if that fails because the server is unreachable, the stale
+/// entry is used anyways, and is not revalidated again for a short while.
+///
+/// The disk cache may be given a byte budget with [`Cache::disk_budget()`];
+/// once it grows past it, the least recently used files are deleted.
 pub struct Cache {
   inner: Mutex<Inner>,
+  disk: Mutex<Option<Disk>>,
+  in_flight: Mutex<HashMap<String, Arc<Flight>>>,
+  aliases: Mutex<Aliases>,
   file_root: Option<PathBuf>,
+  ttl: Option<Duration>,
+  disk_budget: Option<u64>,
+  compression: Compression,
+}
+
+/// Statistics about the usage of a [`Cache`]; see [`Cache::stats()`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+  /// Statistics for the in-memory cache.
+  pub memory: TierStats,
+  /// Statistics for the disk cache.
+  ///
+  /// These are all zero for caches that do not cache to disk.
+  pub disk: TierStats,
+}
+
+/// Information about an entry in a [`Cache`]; see [`Cache::entries()`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CacheEntry {
+  /// The entry's key, i.e., the URL it was fetched from.
+  pub key: String,
+  /// The size of the entry on disk, in bytes; for entries only in memory, the
+  /// size of their serialized form.
+  pub size: u64,
+  /// How long ago the entry was fetched or last revalidated.
+  pub age: Duration,
+  /// Whether the entry is in the memory cache.
+  pub in_memory: bool,
+  /// Whether the entry is in the disk cache.
+  pub on_disk: bool,
+}
+
+/// Statistics about one tier of a [`Cache`]; see [`CacheStats`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TierStats {
+  /// The number of lookups that found an entry in this tier.
+  pub hits: u64,
+  /// The number of lookups that did not find an entry in this tier.
+  pub misses: u64,
+  /// The number of entries evicted from this tier to make room for others.
+  pub evictions: u64,
+  /// The number of entries currently in this tier.
+  pub entries: u64,
+  /// The total size of the entries currently in this tier, in bytes.
+  ///
+  /// For the memory cache, this is the size of the entries' serialized form,
+  /// which only approximates the memory they actually use.
+  pub bytes: u64,
+}
+
+/// Metadata stored alongside each cache entry.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct Meta {
+  /// When this entry was last fetched or revalidated, in seconds since the Unix
+  /// epoch.
+  fetched_at: u64,
+  /// Caching information from the server.
+  freshness: Freshness,
+  /// If revalidating this entry failed, when to next try again, in seconds
+  /// since the Unix epoch; until then, the entry is treated as fresh.
+  #[serde(default, skip_serializing_if = "is_zero")]
+  retry_at: u64,
+}
+
+/// Returns whether `n` is zero, for skipping default fields in [`Meta`].
+fn is_zero(n: &u64) -> bool {
+  *n == 0
+}
+
+impl Meta {
+  /// Creates metadata for an entry fetched just now.
+  fn now(freshness: Freshness) -> Self {
+    Self {
+      fetched_at: unix_now(),
+      freshness,
+      retry_at: 0,
+    }
+  }
+}
+
+/// A lookup of a cache entry that is in progress; see [`Cache::claim()`].
+#[derive(Default)]
+struct Flight {
+  state: Mutex<FlightState>,
+  landed: Condvar,
+}
+
+#[derive(Default)]
+struct FlightState {
+  /// The outcome of the lookup, once it has finished: the value, if it was
+  /// successful.
+  outcome: Option<Option<Arc<dyn Any + Send + Sync>>>,
+  /// Asynchronous waiters to notify once the lookup finishes.
+  #[cfg(feature = "async")]
+  wakers: Vec<oneshot::Sender<()>>,
+}
+
+impl Flight {
+  /// Blocks until the lookup finishes, returning its value if it succeeded.
+  fn wait(&self) -> Option<Arc<dyn Any + Send + Sync>> {
+    let mut state = self.state.lock().unwrap();
+    loop {
+      if let Some(outcome) = &state.outcome {
+        return outcome.clone();
+      }
+      state = self.landed.wait(state).unwrap();
+    }
+  }
+
+  /// Waits for the lookup to finish, returning its value if it succeeded.
+  #[cfg(feature = "async")]
+  async fn wait_async(&self) -> Option<Arc<dyn Any + Send + Sync>> {
+    let landed = {
+      let mut state = self.state.lock().unwrap();
+      if let Some(outcome) = &state.outcome {
+        return outcome.clone();
+      }
+      let (tx, rx) = oneshot::channel();
+      state.wakers.push(tx);
+      rx
+    };
+
+    let _ = landed.await;
+    self.state.lock().unwrap().outcome.clone().flatten()
+  }
+}
+
+/// The caller performing a [`Flight`].
+///
+/// Dropping a [`Pilot`] finishes the flight, waking up everyone waiting on it;
+/// this ensures that they are not left waiting forever if the lookup panics or,
+/// for asynchronous lookups, is cancelled.
+struct Pilot<'a> {
+  cache: &'a Cache,
+  key: String,
+  flight: Arc<Flight>,
+  outcome: Option<Arc<dyn Any + Send + Sync>>,
+}
+
+impl Pilot<'_> {
+  /// Finishes the flight with the given result.
+  fn land<V: Send + Sync + 'static>(mut self, result: &Result<Arc<V>, Error>) {
+    if let Ok(val) = result {
+      self.outcome = Some(Arc::clone(val) as Arc<dyn Any + Send + Sync>);
+    }
+  }
+}
+
+impl Drop for Pilot<'_> {
+  fn drop(&mut self) {
+    self.cache.in_flight.lock().unwrap().remove(&self.key);
+
+    let mut state = self.flight.state.lock().unwrap();
+    state.outcome = Some(self.outcome.take());
+    self.flight.landed.notify_all();
+    #[cfg(feature = "async")]
+    for waker in state.wakers.drain(..) {
+      let _ = waker.send(());
+    }
+  }
+}
+
+/// Downcasts a type-erased cache value.
+fn downcast<V: Send + Sync + 'static>(
+  val: Arc<dyn Any + Send + Sync>,
+) -> Arc<V> {
+  Arc::downcast(val).expect("wrong type in Cache")
+}
+
+/// Returns the current time, in seconds since the Unix epoch.
+fn unix_now() -> u64 {
+  SystemTime::now()
+    .duration_since(SystemTime::UNIX_EPOCH)
+    .map(|d| d.as_secs())
+    .unwrap_or(0)
+}
+
+/// The result of looking up a key in the memory and disk caches.
+enum Lookup<V> {
+  Fresh(Arc<V>),
+  Stale(Arc<V>, Meta),
+  Missing,
+}
+
+/// The magic number at the start of every disk cache entry, which includes the
+/// version of the entry format.
+const DISK_MAGIC: &[u8] = b"pkmn-cache 2\n";
+
+/// How long to keep using a stale entry without revalidating it, after trying
+/// to revalidate it failed.
+const STALE_IF_ERROR: Duration = Duration::from_secs(60);
+
+/// The prefix for temporary files in the disk cache.
+///
+/// Encoded keys never start with a `.`, so these can't collide with entries.
+const TEMP_PREFIX: &str = ".tmp-";
+
+/// How long a temporary file may linger in the disk cache before it is assumed
+/// to have been abandoned by a crashed process.
+const TEMP_LIFETIME: Duration = Duration::from_secs(3600);
+
+/// A compression algorithm for the disk cache; see [`Cache::compress()`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+  /// No compression.
+  None,
+  /// gzip compression.
+  ///
+  /// Requires the `gzip` feature.
+  #[cfg(feature = "gzip")]
+  Gzip,
+  /// Zstandard compression.
+  ///
+  /// Requires the `zstd` feature.
+  #[cfg(feature = "zstd")]
+  Zstd,
+}
+
+impl Compression {
+  /// Compresses `buf`.
+  fn compress(self, buf: &[u8]) -> io::Result<Vec<u8>> {
+    match self {
+      Self::None => Ok(buf.to_vec()),
+      #[cfg(feature = "gzip")]
+      Self::Gzip => {
+        let mut encoder =
+          GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(buf)?;
+        encoder.finish()
+      }
+      #[cfg(feature = "zstd")]
+      Self::Zstd => libzstd::encode_all(buf, 0),
+    }
+  }
+
+  /// Decompresses `buf`.
+  fn decompress(self, buf: &[u8]) -> io::Result<Vec<u8>> {
+    match self {
+      Self::None => Ok(buf.to_vec()),
+      #[cfg(feature = "gzip")]
+      Self::Gzip => {
+        let mut out = Vec::new();
+        GzDecoder::new(buf).read_to_end(&mut out)?;
+        Ok(out)
+      }
+      #[cfg(feature = "zstd")]
+      Self::Zstd => libzstd::decode_all(buf),
+    }
+  }
+}
+
+/// The header of a disk cache entry, which follows [`DISK_MAGIC`].
+///
+/// The header is a single line of JSON, followed by the (possibly compressed)
+/// serialized value.
+#[derive(Serialize, Deserialize)]
+struct Header {
+  /// The version of this library that wrote the entry. The layout of the
+  /// serialized value may differ between versions, so entries from other
+  /// versions are ignored.
+  pkmn_version: String,
+  /// How the value is compressed.
+  compression: Compression,
+  /// The length of the compressed value.
+  len: u64,
+  /// The CRC-32 of the compressed value.
+  checksum: u32,
+  /// The entry's metadata.
+  meta: Meta,
+  /// If this entry is an alias, the key it is an alias for; see
+  /// [`Cache::alias()`]. Aliases have no value.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  alias_of: Option<String>,
+}
+
+impl Header {
+  /// Reads just the header of the disk cache entry at `path`.
+  fn read(path: &Path) -> Option<Header> {
+    let mut file = BufReader::new(File::open(path).ok()?);
+    let mut magic = Vec::new();
+    file.read_until(b'\n', &mut magic).ok()?;
+    if magic != DISK_MAGIC {
+      return None;
+    }
+
+    let mut header = Vec::new();
+    file.read_until(b'\n', &mut header).ok()?;
+    serde_json::from_slice(&header).ok()
+  }
+
+  /// Decodes a disk cache entry, returning its metadata and uncompressed
+  /// value, or `None` if it is corrupt or in a different format.
+  fn decode(buf: &[u8]) -> Option<(Meta, Vec<u8>)> {
+    let (header, body) = Header::split(buf)?;
+    if header.alias_of.is_some() {
+      return None;
+    }
+    let body = header.compression.decompress(body).ok()?;
+    Some((header.meta, body))
+  }
+
+  /// Encodes a disk cache entry with this header and the given compressed
+  /// value.
+  fn encode(&self, body: &[u8]) -> Vec<u8> {
+    let mut buf = DISK_MAGIC.to_vec();
+    serde_json::to_writer(&mut buf, self).unwrap();
+    buf.push(b'\n');
+    buf.extend_from_slice(body);
+    buf
+  }
+
+  /// Splits a disk cache entry into its header and compressed value, checking
+  /// that it is intact and was written by this version of the library.
+  fn split(buf: &[u8]) -> Option<(Header, &[u8])> {
+    let buf = buf.strip_prefix(DISK_MAGIC)?;
+    let idx = buf.iter().position(|&b| b == b'\n')?;
+    let (header, body) = (&buf[..idx], &buf[idx + 1..]);
+
+    let header: Header = serde_json::from_slice(header).ok()?;
+    if header.pkmn_version != env!("CARGO_PKG_VERSION")
+      || header.len != body.len() as u64
+      || header.checksum != crc32fast::hash(body)
+    {
+      return None;
+    }
+    Some((header, body))
+  }
+}
+
+/// The magic number at the start of every bundle written by
+/// [`Cache::export()`], which includes the version of the bundle format.
+const BUNDLE_MAGIC: &[u8] = b"pkmn-cache-bundle 1\n";
+
+/// The manifest of a bundle, which follows [`BUNDLE_MAGIC`].
+///
+/// The manifest is a single line of JSON, followed by the disk cache entries
+/// it lists, verbatim and in order.
+#[derive(Serialize, Deserialize)]
+struct Manifest {
+  /// The version of this library that wrote the bundle.
+  pkmn_version: String,
+  /// The entries in the bundle.
+  entries: Vec<ManifestEntry>,
+}
+
+/// An entry in a [`Manifest`].
+#[derive(Serialize, Deserialize)]
+struct ManifestEntry {
+  /// The URL the entry was fetched from.
+  url: String,
+  /// The name of the endpoint the URL belongs to, such as `"pokemon"`, if it
+  /// is a PokéAPI URL.
+  endpoint: Option<String>,
+  /// When the entry was fetched, in seconds since the Unix epoch.
+  fetched_at: u64,
+  /// The length of the entry, in bytes.
+  len: u64,
+}
+
+/// Returns the name of the PokéAPI endpoint `url` belongs to, if any.
+fn endpoint_of(url: &str) -> Option<String> {
+  let (_, path) = url.split_once("/api/v2/")?;
+  let endpoint = path.split(['/', '?']).next()?;
+  Some(endpoint.to_string()).filter(|e| !e.is_empty())
+}
+
+/// The aliases for entries in the memory cache; see [`Cache::alias()`].
+///
+/// An alias is only kept here while the entry it points to is in memory, and
+/// is dropped when that entry is evicted; this keeps the number of aliases
+/// bounded by the memory cache's capacity. Aliases are also written to disk,
+/// so dropping them here only costs a file read the next time they are used.
+#[derive(Default)]
+struct Aliases {
+  /// The key each alias points to.
+  targets: HashMap<String, String>,
+  /// The aliases pointing to each key.
+  by_target: HashMap<String, Vec<String>>,
+}
+
+impl Aliases {
+  /// Records that `k` is an alias for `target`.
+  fn insert(&mut self, k: &str, target: &str) {
+    self.remove(k);
+    self.targets.insert(k.to_string(), target.to_string());
+    self
+      .by_target
+      .entry(target.to_string())
+      .or_default()
+      .push(k.to_string());
+  }
+
+  /// Removes the alias `k`, returning whether it was present.
+  fn remove(&mut self, k: &str) -> bool {
+    let target = match self.targets.remove(k) {
+      Some(target) => target,
+      None => return false,
+    };
+    if let Some(aliases) = self.by_target.get_mut(&target) {
+      aliases.retain(|a| a != k);
+      if aliases.is_empty() {
+        self.by_target.remove(&target);
+      }
+    }
+    true
+  }
+
+  /// Removes every alias pointing to `target`.
+  fn evict(&mut self, target: &str) {
+    for k in self.by_target.remove(target).unwrap_or_default() {
+      self.targets.remove(&k);
+    }
+  }
 }
 
 struct Inner {
   map: HashMap<WeakString, Box<Entry>>,
   capacity: usize,
+  stats: TierStats,
 
   head: *mut Entry,
   tail: *mut Entry,
 }
 unsafe impl Send for Inner {}
 
+/// An index of the files in the disk cache, in LRU order.
+///
+/// This is built by scanning the cache directory the first time the disk cache
+/// is touched, using file modification times as the initial LRU order; from
+/// then on, it is kept up to date as files are read and written. Reads are not
+/// written back to disk, so across runs, the LRU order is really the order in
+/// which files were written.
+struct Disk {
+  /// The size and last use of each file, by name.
+  files: HashMap<String, (u64, u64)>,
+  /// File names, by last use.
+  lru: BTreeMap<u64, String>,
+  /// A counter for ordering file uses.
+  clock: u64,
+  stats: TierStats,
+}
+
 struct WeakString(*const str);
 impl Hash for WeakString {
   fn hash<H: Hasher>(&self, state: &mut H) {
@@ -83,6 +557,7 @@ impl Cache {
     let inner = Inner {
       map: HashMap::new(),
       capacity,
+      stats: TierStats::default(),
       // The head and tail are "empty" nodes, to make attach/detach simpler.
       head: Box::into_raw(Box::new(Entry::sigil())),
       tail: Box::into_raw(Box::new(Entry::sigil())),
@@ -95,158 +570,921 @@ impl Cache {
 
     Cache {
       inner: Mutex::new(inner),
+      disk: Mutex::new(None),
+      in_flight: Mutex::new(HashMap::new()),
+      aliases: Mutex::new(Aliases::default()),
       file_root,
+      ttl: None,
+      disk_budget: None,
+      compression: Compression::None,
+    }
+  }
+
+  /// Sets the time-to-live for entries in this cache, overriding the one
+  /// specified by the server.
+  ///
+  /// Entries older than `ttl` will be revalidated before being used.
+  #[inline]
+  pub fn expire_after(mut self, ttl: Duration) -> Self {
+    self.ttl = Some(ttl);
+    self
+  }
+
+  /// Sets the maximum size of the disk cache, in bytes.
+  ///
+  /// Whenever the disk cache grows past `bytes`, the least recently used
+  /// entries are deleted until it fits again. By default, the disk cache is
+  /// unbounded.
+  #[inline]
+  pub fn disk_budget(mut self, bytes: u64) -> Self {
+    self.disk_budget = Some(bytes);
+    self
+  }
+
+  /// Sets the compression algorithm for new entries in the disk cache.
+  ///
+  /// Entries that were compressed with a different algorithm can still be
+  /// read, as long as support for it is enabled. By default, entries are not
+  /// compressed.
+  #[inline]
+  pub fn compress(mut self, compression: Compression) -> Self {
+    self.compression = compression;
+    self
+  }
+
+  /// Resolves `k`, if it is an alias for another key; see [`Cache::alias()`].
+  ///
+  /// Aliases found on disk are not remembered in memory, since their targets
+  /// may not be; see [`Cache::remember_alias()`].
+  pub(in crate::api) fn resolve(&self, k: &str) -> Option<String> {
+    if let Some(target) = self.aliases.lock().unwrap().targets.get(k) {
+      return Some(target.clone());
     }
+
+    let path = self.file_root.as_ref()?.join(Self::encode_key(k));
+    Header::read(&path)?.alias_of
+  }
+
+  /// Records that `k` is an alias for `target`, such as a resource's URL by
+  /// name for its URL by ID, so that both can share a single entry.
+  ///
+  /// Aliases never go stale. Writing them to disk is best-effort.
+  pub(in crate::api) fn alias(&self, k: &str, target: &str) {
+    self.remember_alias(k, target);
+
+    let root = match &self.file_root {
+      Some(root) => root,
+      None => return,
+    };
+    if !root.exists() && fs::create_dir_all(root).is_err() {
+      return;
+    }
+    let header = Header {
+      pkmn_version: env!("CARGO_PKG_VERSION").to_string(),
+      compression: Compression::None,
+      len: 0,
+      checksum: crc32fast::hash(&[]),
+      meta: Meta::now(Freshness::default()),
+      alias_of: Some(target.to_string()),
+    };
+    let _ = self.write_file(root, &Self::encode_key(k), &header.encode(&[]));
+  }
+
+  /// Records that `k` is an alias for `target` in memory only, if `target` is
+  /// in the memory cache.
+  pub(in crate::api) fn remember_alias(&self, k: &str, target: &str) {
+    // `target` may be evicted before the alias is inserted, leaving it
+    // orphaned until `target` is cached and evicted again; that's harmless.
+    if self
+      .inner
+      .lock()
+      .unwrap()
+      .map
+      .contains_key(&WeakString(target))
+    {
+      self.aliases.lock().unwrap().insert(k, target);
+    }
+  }
+
+  /// Returns statistics about this cache's usage so far.
+  pub fn stats(&self) -> CacheStats {
+    let memory = self.inner.lock().unwrap().stats;
+    let disk = self.with_disk(|disk| disk.stats).unwrap_or_default();
+    CacheStats { memory, disk }
+  }
+
+  /// Removes the entry with the given key (i.e., the URL it was fetched from)
+  /// from both the memory and disk caches.
+  ///
+  /// Returns whether there was such an entry.
+  pub fn invalidate(&self, key: &str) -> bool {
+    let aliased = {
+      let mut aliases = self.aliases.lock().unwrap();
+      aliases.evict(key);
+      aliases.remove(key)
+    };
+    let in_memory = self.inner.lock().unwrap().remove(key);
+    let on_disk = match &self.file_root {
+      Some(root) => {
+        let name = Self::encode_key(key);
+        self.with_disk(|disk| disk.forget(&name));
+        fs::remove_file(root.join(&name)).is_ok()
+      }
+      None => false,
+    };
+    aliased || in_memory || on_disk
+  }
+
+  /// Removes every entry from both the memory and disk caches.
+  ///
+  /// Removing entries from disk is best-effort; files that cannot be removed
+  /// are left behind.
+  pub fn clear(&self) {
+    *self.aliases.lock().unwrap() = Aliases::default();
+    self.inner.lock().unwrap().clear();
+    if let Some(root) = &self.file_root {
+      self.with_disk(|disk| {
+        while let Some(name) = disk.pop_lru() {
+          let _ = fs::remove_file(root.join(name));
+        }
+      });
+    }
+  }
+
+  /// Writes the entries in this cache's disk cache to a bundle file at `path`,
+  /// returning how many were written.
+  ///
+  /// The bundle can be loaded into another cache (possibly on another
+  /// machine) with [`Cache::import()`]. Only entries on disk are exported;
+  /// it is an error to export a cache that does not cache to disk.
+  pub fn export(&self, path: impl AsRef<Path>) -> io::Result<usize> {
+    let root = self.disk_root()?;
+    let names = self
+      .with_disk(|disk| disk.files.keys().cloned().collect::<Vec<_>>())
+      .unwrap_or_default();
+
+    // The manifest comes first, so only read headers for now; the entries
+    // themselves are copied into the bundle afterwards, one at a time.
+    let mut manifest = Manifest {
+      pkmn_version: env!("CARGO_PKG_VERSION").to_string(),
+      entries: Vec::new(),
+    };
+    let mut files = Vec::new();
+    for name in names {
+      let url = match Self::decode_key(&name) {
+        Some(url) => url,
+        None => continue,
+      };
+      // Entries may be evicted out from under us; skip those, along with any
+      // that were written by another version.
+      let path = root.join(&name);
+      let header = match Header::read(&path) {
+        Some(header) if header.pkmn_version == env!("CARGO_PKG_VERSION") => {
+          header
+        }
+        _ => continue,
+      };
+      let len = match fs::metadata(&path) {
+        Ok(meta) => meta.len(),
+        Err(_) => continue,
+      };
+
+      manifest.entries.push(ManifestEntry {
+        endpoint: endpoint_of(&url),
+        url,
+        fetched_at: header.meta.fetched_at,
+        len,
+      });
+      files.push(path);
+    }
+
+    let mut out = BufWriter::new(File::create(path)?);
+    out.write_all(BUNDLE_MAGIC)?;
+    serde_json::to_writer(&mut out, &manifest)?;
+    out.write_all(b"\n")?;
+    for (entry, path) in manifest.entries.iter().zip(&files) {
+      // If an entry changed since its header was read, it no longer matches
+      // the manifest; keep the bundle's layout intact by writing exactly as
+      // many bytes as promised. The mangled entry fails its checksum, so
+      // `import()` skips it.
+      let copied = match File::open(path) {
+        Ok(file) => io::copy(&mut file.take(entry.len), &mut out)?,
+        Err(_) => 0,
+      };
+      io::copy(&mut io::repeat(0).take(entry.len - copied), &mut out)?;
+    }
+    out.flush()?;
+    Ok(files.len())
+  }
+
+  /// Merges the entries in a bundle file written by [`Cache::export()`] into
+  /// this cache's disk cache, returning how many were imported.
+  ///
+  /// Entries in the bundle replace existing ones only if they were fetched
+  /// more recently. Entries that are corrupt or were written by a different
+  /// version of this library are skipped.
+  pub fn import(&self, path: impl AsRef<Path>) -> io::Result<usize> {
+    let root = self.disk_root()?;
+    if !root.exists() {
+      fs::create_dir_all(root)?;
+    }
+
+    let mut bundle = BufReader::new(File::open(path)?);
+    let mut magic = Vec::new();
+    bundle.read_until(b'\n', &mut magic)?;
+    if magic != BUNDLE_MAGIC {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "not a pkmn cache bundle",
+      ));
+    }
+    let mut manifest = Vec::new();
+    bundle.read_until(b'\n', &mut manifest)?;
+    let manifest: Manifest = serde_json::from_slice(&manifest)?;
+
+    let mut count = 0;
+    for entry in manifest.entries {
+      // Don't trust the manifest's lengths enough to allocate them up front.
+      let mut buf = Vec::new();
+      (&mut bundle).take(entry.len).read_to_end(&mut buf)?;
+      if (buf.len() as u64) < entry.len {
+        return Err(io::Error::new(
+          io::ErrorKind::UnexpectedEof,
+          "truncated pkmn cache bundle",
+        ));
+      }
+      if Header::split(&buf).is_none() {
+        continue;
+      }
+
+      let name = Self::encode_key(&entry.url);
+      let newer = match Header::read(&root.join(&name)) {
+        Some(existing) => existing.meta.fetched_at < entry.fetched_at,
+        None => true,
+      };
+      if !newer {
+        continue;
+      }
+
+      self.write_file(root, &name, &buf)?;
+      self.inner.lock().unwrap().remove(&entry.url);
+      self.aliases.lock().unwrap().evict(&entry.url);
+      count += 1;
+    }
+    Ok(count)
+  }
+
+  /// Returns the disk cache's directory, or an error if there isn't one.
+  fn disk_root(&self) -> io::Result<&Path> {
+    self
+      .file_root
+      .as_deref()
+      .ok_or_else(|| io::Error::other("cache does not cache to disk"))
+  }
+
+  /// Returns an iterator over the entries in this cache.
+  ///
+  /// Entries in memory come first, from most to least recently used, followed
+  /// by entries only on disk. This is a snapshot: entries added or removed
+  /// while iterating may or may not be observed.
+  pub fn entries(&self) -> impl Iterator<Item = CacheEntry> + '_ {
+    let now = unix_now();
+    let age =
+      move |fetched_at| Duration::from_secs(now.saturating_sub(fetched_at));
+
+    let memory = self.inner.lock().unwrap().snapshot();
+    let mut disk = self
+      .with_disk(|disk| {
+        disk
+          .files
+          .iter()
+          .map(|(name, &(size, _))| (name.clone(), size))
+          .collect::<HashMap<_, _>>()
+      })
+      .unwrap_or_default();
+
+    let memory = memory
+      .into_iter()
+      .map(|(key, size, fetched_at)| {
+        let on_disk = disk.remove(&Self::encode_key(&key));
+        CacheEntry {
+          key,
+          size: on_disk.unwrap_or(size as u64),
+          age: age(fetched_at),
+          in_memory: true,
+          on_disk: on_disk.is_some(),
+        }
+      })
+      .collect::<Vec<_>>();
+
+    // Entries only on disk need to have their headers read to find out their
+    // age, so do that lazily.
+    let disk = disk.into_iter().filter_map(move |(name, size)| {
+      let key = Self::decode_key(&name)?;
+      let header = Header::read(&self.file_root.as_ref()?.join(&name))?;
+      if header.alias_of.is_some() {
+        return None;
+      }
+      Some(CacheEntry {
+        key,
+        size,
+        age: age(header.meta.fetched_at),
+        in_memory: false,
+        on_disk: true,
+      })
+    });
+
+    memory.into_iter().chain(disk)
   }
 
   /// Looks up a value of type `V` with the given key.
   ///
   /// First, this function checks the in-memory cache; then it checks the disk
-  /// cache. If both of those fails, `f` is called to perform the computation.
+  /// cache. If both of those fails, or the value found is stale, `compute` is
+  /// called to perform the computation, with the caching information of the
+  /// stale value, if there was one.
+  ///
+  /// Any errors produced by `compute` will bubble up to the caller, unless they
+  /// are transient and there is a stale value to fall back on.
   ///
-  /// Any errors produced by `f` will bubble up to the caller.
+  /// Only one caller looks up a given key at a time; concurrent callers for
+  /// the same key wait for it to finish and share its result. If it fails, they
+  /// try again themselves.
   pub(in crate::api) fn get<V: Send + Sync + 'static>(
     &self,
     k: &str,
     deserialize: impl FnOnce(Vec<u8>) -> Result<V, Error>,
     serialize: impl FnOnce(&V) -> Result<Vec<u8>, Error>,
-    compute: impl FnOnce() -> Result<V, Error>,
+    compute: impl FnOnce(Option<&Freshness>) -> Result<Conditional<V>, Error>,
   ) -> Result<Arc<V>, Error> {
-    let mut inner = self.inner.lock().unwrap();
-    if let Some(node) = inner.map.get_mut(&WeakString(k)) {
-      // Pull a node out of the memory cache if one is present.
-      unsafe {
-        let node_ptr: *mut _ = &mut **node;
+    let pilot = loop {
+      match self.claim(k) {
+        Ok(pilot) => break pilot,
+        Err(flight) => {
+          if let Some(val) = flight.wait() {
+            return Ok(downcast(val));
+          }
+        }
+      }
+    };
+
+    let result = (|| {
+      let stale = match self.lookup(k, deserialize)? {
+        Lookup::Fresh(val) => return Ok(val),
+        Lookup::Stale(val, meta) => Some((val, meta)),
+        Lookup::Missing => None,
+      };
+
+      let result = compute(stale.as_ref().map(|(_, meta)| &meta.freshness));
+      self.settle(k, stale, result, serialize)
+    })();
+    pilot.land(&result);
+    result
+  }
+
+  /// Looks up a value of type `V` with the given key, asynchronously.
+  ///
+  /// This function behaves like [`Cache::get()`], except that `compute` is
+  /// allowed to be asynchronous.
+  #[cfg(feature = "async")]
+  pub(in crate::api) async fn get_async<V, F>(
+    &self,
+    k: &str,
+    deserialize: impl FnOnce(Vec<u8>) -> Result<V, Error>,
+    serialize: impl FnOnce(&V) -> Result<Vec<u8>, Error>,
+    compute: impl FnOnce(Option<Freshness>) -> F,
+  ) -> Result<Arc<V>, Error>
+  where
+    V: Send + Sync + 'static,
+    F: std::future::Future<Output = Result<Conditional<V>, Error>>,
+  {
+    let pilot = loop {
+      match self.claim(k) {
+        Ok(pilot) => break pilot,
+        Err(flight) => {
+          if let Some(val) = flight.wait_async().await {
+            return Ok(downcast(val));
+          }
+        }
+      }
+    };
+
+    let result = async {
+      let stale = match self.lookup(k, deserialize)? {
+        Lookup::Fresh(val) => return Ok(val),
+        Lookup::Stale(val, meta) => Some((val, meta)),
+        Lookup::Missing => None,
+      };
 
-        inner.detach(node_ptr);
-        inner.attach(node_ptr);
+      let cached = stale.as_ref().map(|(_, meta)| meta.freshness.clone());
+      let result = compute(cached).await;
+      self.settle(k, stale, result, serialize)
+    }
+    .await;
+    pilot.land(&result);
+    result
+  }
 
-        let rc = Arc::clone(&*(*node_ptr).val.as_ptr());
-        return Ok(Arc::downcast(rc).expect("wrong type in FileCache"));
+  /// Claims the right to look up `k`.
+  ///
+  /// If no one else is looking up `k`, this returns a [`Pilot`], which
+  /// publishes the result to anyone else who tries to claim `k` in the
+  /// meantime. Otherwise, it returns the [`Flight`] to wait on.
+  fn claim(&self, k: &str) -> Result<Pilot<'_>, Arc<Flight>> {
+    let mut in_flight = self.in_flight.lock().unwrap();
+    if let Some(flight) = in_flight.get(k) {
+      return Err(Arc::clone(flight));
+    }
+
+    let flight = Arc::new(Flight::default());
+    in_flight.insert(k.to_string(), Arc::clone(&flight));
+    Ok(Pilot {
+      cache: self,
+      key: k.to_string(),
+      flight,
+      outcome: None,
+    })
+  }
+
+  /// Looks up a value in the memory cache, and then in the disk cache.
+  fn lookup<V: Send + Sync + 'static>(
+    &self,
+    k: &str,
+    deserialize: impl FnOnce(Vec<u8>) -> Result<V, Error>,
+  ) -> Result<Lookup<V>, Error> {
+    // The memory cache is always at least as up-to-date as the disk cache, so
+    // there's no point in going to disk for a stale entry.
+    if let Some((val, meta)) = self.recall(k) {
+      if self.is_fresh(&meta) {
+        return Ok(Lookup::Fresh(val));
       }
+      return Ok(Lookup::Stale(val, meta));
     }
-    drop(inner);
 
-    let val = match self.unearth(k, deserialize)? {
-      Some(x) => x,
-      None => {
-        let val = Arc::new(compute()?);
-        self.bury(k, &*val, serialize)?;
-        val
+    match self.unearth(k, deserialize)? {
+      Some((val, meta, size)) if self.is_fresh(&meta) => {
+        self.remember(k, &val, meta, size)?;
+        Ok(Lookup::Fresh(val))
       }
+      Some((val, meta, _)) => Ok(Lookup::Stale(val, meta)),
+      None => Ok(Lookup::Missing),
+    }
+  }
+
+  /// Records the result of computing a value that was missing or stale.
+  fn settle<V: Send + Sync + 'static>(
+    &self,
+    k: &str,
+    stale: Option<(Arc<V>, Meta)>,
+    result: Result<Conditional<V>, Error>,
+    serialize: impl FnOnce(&V) -> Result<Vec<u8>, Error>,
+  ) -> Result<Arc<V>, Error> {
+    let (val, meta) = match (result, stale) {
+      (Ok(Conditional::Modified(val, freshness)), _) => {
+        (Arc::new(val), Meta::now(freshness))
+      }
+      (Ok(Conditional::NotModified(freshness)), Some((val, mut meta))) => {
+        meta.freshness.refresh(freshness);
+        meta.fetched_at = unix_now();
+        meta.retry_at = 0;
+        (val, meta)
+      }
+      (Ok(Conditional::NotModified(_)), None) => {
+        return Err(Error::new(k, ErrorKind::Status(304)))
+      }
+      // If we can't reach the server, a stale value is better than nothing.
+      // Hold on to it for a little while, rather than asking the server again
+      // on every lookup; this only touches the memory cache, so the entry on
+      // disk stays stale.
+      (Err(e), Some((val, mut meta))) if e.kind.is_transient() => {
+        let mut wait = STALE_IF_ERROR;
+        if let ErrorKind::RateLimited {
+          retry_after: Some(retry_after),
+          ..
+        } = e.kind
+        {
+          wait = wait.max(retry_after);
+        }
+        meta.retry_at = unix_now() + wait.as_secs();
+        if let Ok(buf) = serialize(&*val) {
+          self.remember(k, &val, meta, buf.len())?;
+        }
+        return Ok(val);
+      }
+      (Err(e), _) => return Err(e),
     };
 
-    let clone = Arc::clone(&val) as Arc<(dyn Any + Send + Sync + 'static)>;
-    self.inner.lock().unwrap().insert(k.to_string(), clone)?;
+    let buf = serialize(&*val)?;
+    self.bury(k, &buf, &meta)?;
+    self.remember(k, &val, meta, buf.len())?;
     Ok(val)
   }
 
-  /// Writes a key/value pair to the disk cache.
-  fn bury<V>(
+  /// Returns whether an entry with the given metadata is still fresh.
+  fn is_fresh(&self, meta: &Meta) -> bool {
+    if unix_now() < meta.retry_at {
+      return true;
+    }
+    match self.ttl.or(meta.freshness.max_age) {
+      Some(ttl) => unix_now().saturating_sub(meta.fetched_at) < ttl.as_secs(),
+      None => true,
+    }
+  }
+
+  /// Try to pull a value of type `V` out of the memory cache.
+  fn recall<V: Send + Sync + 'static>(
     &self,
     k: &str,
-    v: &V,
-    serialize: impl FnOnce(&V) -> Result<Vec<u8>, Error>,
+  ) -> Option<(Arc<V>, Meta)> {
+    let mut inner = self.inner.lock().unwrap();
+    let node = match inner.map.get_mut(&WeakString(k)) {
+      Some(node) => node,
+      None => {
+        inner.stats.misses += 1;
+        return None;
+      }
+    };
+
+    // Pull a node out of the memory cache if one is present.
+    unsafe {
+      let node_ptr: *mut _ = &mut **node;
+
+      inner.stats.hits += 1;
+      inner.detach(node_ptr);
+      inner.attach(node_ptr);
+
+      let rc = Arc::clone(&*(*node_ptr).val.as_ptr());
+      let val = downcast(rc);
+      Some((val, (*node_ptr).meta.clone()))
+    }
+  }
+
+  /// Writes a key/value pair to the memory cache.
+  ///
+  /// `size` is the size of the value's serialized form.
+  fn remember<V: Send + Sync + 'static>(
+    &self,
+    k: &str,
+    v: &Arc<V>,
+    meta: Meta,
+    size: usize,
   ) -> Result<(), Error> {
-    let mut path = match &self.file_root {
+    let clone = Arc::clone(v) as Arc<dyn Any + Send + Sync + 'static>;
+    let evicted =
+      self
+        .inner
+        .lock()
+        .unwrap()
+        .insert(k.to_string(), clone, meta, size);
+    if let Some(evicted) = evicted {
+      self.aliases.lock().unwrap().evict(&evicted);
+    }
+    Ok(())
+  }
+
+  /// Writes a key and serialized value to the disk cache.
+  ///
+  /// The entry is written to a temporary file first, and then moved into
+  /// place, so that other readers never observe a partially-written entry.
+  fn bury(&self, k: &str, v: &[u8], meta: &Meta) -> Result<(), Error> {
+    let root = match &self.file_root {
       Some(path) => {
-        if !path.exists() {
-          if fs::create_dir_all(path).is_err() {
-            return Ok(());
-          }
+        if !path.exists() && fs::create_dir_all(path).is_err() {
+          return Ok(());
         }
-        path.clone()
+        path
       }
       None => return Ok(()),
     };
 
-    path.push(&Self::encode_key(k));
+    let name = Self::encode_key(k);
+    let body = self.compression.compress(v).map_err(|e| Error::new(k, e))?;
+    let header = Header {
+      pkmn_version: env!("CARGO_PKG_VERSION").to_string(),
+      compression: self.compression,
+      len: body.len() as u64,
+      checksum: crc32fast::hash(&body),
+      meta: meta.clone(),
+      alias_of: None,
+    };
+
+    self
+      .write_file(root, &name, &header.encode(&body))
+      .map_err(|e| Error::new(k, e))
+  }
+
+  /// Writes a file into the disk cache, via a temporary file.
+  fn write_file(&self, root: &Path, name: &str, buf: &[u8]) -> io::Result<()> {
+    static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let temp = root.join(format!(
+      "{}{}.{}.{}",
+      TEMP_PREFIX,
+      name,
+      process::id(),
+      TEMP_COUNTER.fetch_add(1, Ordering::Relaxed),
+    ));
+    let result =
+      fs::write(&temp, buf).and_then(|_| fs::rename(&temp, root.join(name)));
+    if let Err(e) = result {
+      let _ = fs::remove_file(&temp);
+      return Err(e);
+    }
 
-    fs::write(&path, serialize(v)?).map_err(|e| Error::new(k, e))?;
+    self.with_disk(|disk| {
+      disk.touch(name, Some(buf.len() as u64));
+      if let Some(budget) = self.disk_budget {
+        self.evict_to(disk, budget);
+      }
+    });
     Ok(())
   }
 
   /// Try to pull a value of type `V` out of the disk cache.
+  ///
+  /// Entries that are corrupt, that are not in the current format, or that
+  /// were written by a different version of this library are treated as
+  /// missing.
   fn unearth<V>(
     &self,
     k: &str,
     deserialize: impl FnOnce(Vec<u8>) -> Result<V, Error>,
-  ) -> Result<Option<Arc<V>>, Error> {
+  ) -> Result<Option<(Arc<V>, Meta, usize)>, Error> {
     let mut path = match &self.file_root {
       Some(path) => {
-        if !path.exists() {
-          if fs::create_dir_all(path).is_err() {
-            return Ok(None);
-          }
+        if !path.exists() && fs::create_dir_all(path).is_err() {
+          return Ok(None);
         }
         path.clone()
       }
       None => return Ok(None),
     };
 
-    path.push(&Self::encode_key(k));
-    if !path.exists() {
-      return Ok(None);
-    }
+    let name = Self::encode_key(k);
+    path.push(&name);
+
+    let entry = fs::read(&path).ok().and_then(|buf| {
+      let (meta, body) = Header::decode(&buf)?;
+      let val = deserialize(body.clone()).ok()?;
+      Some((Arc::new(val), meta, body.len()))
+    });
+
+    // Only the index's LRU order is updated; rewriting the file's modification
+    // time on every hit would turn each read into a write.
+    self.with_disk(|disk| match &entry {
+      Some(_) => {
+        disk.stats.hits += 1;
+        disk.touch(&name, None);
+      }
+      None => disk.stats.misses += 1,
+    });
+
+    Ok(entry)
+  }
+
+  /// Runs `f` on the disk cache's index, loading it if necessary.
+  ///
+  /// Returns `None` if this cache does not cache to disk.
+  fn with_disk<R>(&self, f: impl FnOnce(&mut Disk) -> R) -> Option<R> {
+    let root = self.file_root.as_ref()?;
+    let mut disk = self.disk.lock().unwrap();
+    Some(f(disk.get_or_insert_with(|| Disk::scan(root))))
+  }
+
+  /// Deletes the least recently used files in the disk cache until it fits in
+  /// `budget` bytes.
+  fn evict_to(&self, disk: &mut Disk, budget: u64) {
+    let root = match &self.file_root {
+      Some(root) => root,
+      None => return,
+    };
 
-    let val = deserialize(fs::read(path).map_err(|e| Error::new(k, e))?)?;
-    Ok(Some(Arc::new(val)))
+    while disk.stats.bytes > budget {
+      let name = match disk.pop_lru() {
+        Some(name) => name,
+        None => break,
+      };
+      let _ = fs::remove_file(root.join(name));
+      disk.stats.evictions += 1;
+    }
   }
 
   /// Encodes `key` for the purposes of being a file name for the disk cache.
   fn encode_key(key: &str) -> String {
     base64::encode_config(key.as_bytes(), base64::URL_SAFE)
   }
+
+  /// Decodes a disk cache file name back into a key.
+  fn decode_key(name: &str) -> Option<String> {
+    let bytes = base64::decode_config(name, base64::URL_SAFE).ok()?;
+    String::from_utf8(bytes).ok()
+  }
+}
+
+impl Disk {
+  /// Builds an index of the files in `root`.
+  fn scan(root: &Path) -> Self {
+    let mut files = match fs::read_dir(root) {
+      Ok(dir) => dir
+        .filter_map(|entry| {
+          let entry = entry.ok()?;
+          let meta = entry.metadata().ok()?;
+          if !meta.is_file() {
+            return None;
+          }
+          let name = entry.file_name().into_string().ok()?;
+          let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+          if name.starts_with(TEMP_PREFIX) {
+            let age = modified.elapsed().unwrap_or_default();
+            if age > TEMP_LIFETIME {
+              let _ = fs::remove_file(entry.path());
+            }
+            return None;
+          }
+          Some((modified, name, meta.len()))
+        })
+        .collect::<Vec<_>>(),
+      Err(_) => Vec::new(),
+    };
+    files.sort();
+
+    let mut disk = Disk {
+      files: HashMap::new(),
+      lru: BTreeMap::new(),
+      clock: 0,
+      stats: TierStats::default(),
+    };
+    for (_, name, size) in files {
+      disk.touch(&name, Some(size));
+    }
+    disk
+  }
+
+  /// Marks the file `name` as just used, and updates its size if it was
+  /// written.
+  fn touch(&mut self, name: &str, written: Option<u64>) {
+    self.clock += 1;
+    let clock = self.clock;
+    match self.files.get_mut(name) {
+      Some((size, used)) => {
+        self.lru.remove(used);
+        *used = clock;
+        if let Some(written) = written {
+          self.stats.bytes -= *size;
+          self.stats.bytes += written;
+          *size = written;
+        }
+      }
+      // A file we read but didn't know about, probably because another
+      // process wrote it; we don't know how big it is, so leave it be.
+      None if written.is_none() => return,
+      None => {
+        let size = written.unwrap_or_default();
+        self.files.insert(name.to_string(), (size, clock));
+        self.stats.bytes += size;
+        self.stats.entries += 1;
+      }
+    }
+    self.lru.insert(clock, name.to_string());
+  }
+
+  /// Removes the least recently used file from the index, returning its name.
+  fn pop_lru(&mut self) -> Option<String> {
+    let name = self.lru.values().next()?.clone();
+    self.forget(&name);
+    Some(name)
+  }
+
+  /// Removes the file `name` from the index.
+  fn forget(&mut self, name: &str) {
+    if let Some((size, used)) = self.files.remove(name) {
+      self.lru.remove(&used);
+      self.stats.bytes -= size;
+      self.stats.entries -= 1;
+    }
+  }
 }
 
 impl Inner {
-  /// Inserts a type-erased value.
+  /// Inserts a type-erased value, returning the key of the value evicted to
+  /// make room for it, if any.
+  ///
+  /// `size` is the size of the value's serialized form.
   fn insert(
     &mut self,
     k: String,
-    v: Arc<(dyn Any + Send + Sync + 'static)>,
-  ) -> Result<(), Error> {
+    v: Arc<dyn Any + Send + Sync + 'static>,
+    meta: Meta,
+    size: usize,
+  ) -> Option<String> {
     // If the capacity is zero, do nothing.
     if self.capacity == 0 {
-      return Ok(());
+      return None;
     }
 
-    let node_ptr = self.map.get_mut(&WeakString(&*k)).map(|v| &mut **v);
-    if node_ptr.is_some() {
-      // The key is already present; this is a bug.
-      panic!("attenpted to re-insert already-cached key");
+    // If the key is already present, as happens when a stale entry gets
+    // refreshed, replace its value in-place.
+    if let Some(node) = self.map.get_mut(&WeakString(&*k)) {
+      let node_ptr: *mut Entry = &mut **node;
+      unsafe {
+        *(*node_ptr).val.as_mut_ptr() = v;
+        (*node_ptr).meta = meta;
+        self.stats.bytes -= (*node_ptr).size as u64;
+        self.stats.bytes += size as u64;
+        (*node_ptr).size = size;
+        self.detach(node_ptr);
+        self.attach(node_ptr);
+      }
+      return None;
     }
 
+    let mut evicted = None;
     let mut node = if self.map.len() == self.capacity {
       // If the cache is full, we need to evict the last entry.
       let last_entry = unsafe { &*(*self.tail).prev };
       let old_key = unsafe { WeakString(&**last_entry.key.as_ptr()) };
       let mut old_node = self.map.remove(&old_key).unwrap();
+      self.stats.evictions += 1;
+      self.stats.entries -= 1;
+      self.stats.bytes -= old_node.size as u64;
 
       // Evict the old values into the file cache.
       unsafe {
-        ptr::drop_in_place(old_node.key.as_mut_ptr());
+        evicted = Some(ptr::read(old_node.key.as_ptr()));
         ptr::drop_in_place(old_node.val.as_mut_ptr());
       }
 
       old_node.key = MaybeUninit::new(k);
       old_node.val = MaybeUninit::new(v);
+      old_node.meta = meta;
+      old_node.size = size;
 
       unsafe {
         self.detach(&mut *old_node);
       }
       old_node
     } else {
-      Box::new(Entry::new(k, v))
+      Box::new(Entry::new(k, v, meta, size))
     };
 
     unsafe {
       self.attach(&mut *node);
     }
 
+    self.stats.entries += 1;
+    self.stats.bytes += size as u64;
+
     let key = unsafe { WeakString(&**node.key.as_ptr()) };
     self.map.insert(key, node);
-    Ok(())
+    evicted
+  }
+
+  /// Removes the value with the given key, returning whether it was present.
+  fn remove(&mut self, k: &str) -> bool {
+    let mut node = match self.map.remove(&WeakString(k)) {
+      Some(node) => node,
+      None => return false,
+    };
+
+    self.stats.entries -= 1;
+    self.stats.bytes -= node.size as u64;
+    unsafe {
+      self.detach(&mut *node);
+      ptr::drop_in_place(node.key.as_mut_ptr());
+      ptr::drop_in_place(node.val.as_mut_ptr());
+    }
+    true
+  }
+
+  /// Removes every value.
+  fn clear(&mut self) {
+    for (_, mut node) in self.map.drain() {
+      unsafe {
+        ptr::drop_in_place(node.key.as_mut_ptr());
+        ptr::drop_in_place(node.val.as_mut_ptr());
+      }
+    }
+
+    unsafe {
+      (*self.head).next = self.tail;
+      (*self.tail).prev = self.head;
+    }
+    self.stats.entries = 0;
+    self.stats.bytes = 0;
+  }
+
+  /// Returns the key, size and fetch time of every value, from most to least
+  /// recently used.
+  fn snapshot(&self) -> Vec<(String, usize, u64)> {
+    let mut entries = Vec::with_capacity(self.map.len());
+    unsafe {
+      let mut node = (*self.head).next;
+      while node != self.tail {
+        let key = (*(*node).key.as_ptr()).clone();
+        entries.push((key, (*node).size, (*node).meta.fetched_at));
+        node = (*node).next;
+      }
+    }
+    entries
   }
 
   /// Removes a node from the LRU list.
@@ -284,17 +1522,26 @@ impl Drop for Cache {
 
 struct Entry {
   key: MaybeUninit<String>,
-  val: MaybeUninit<Arc<(dyn Any + Send + Sync + 'static)>>,
+  val: MaybeUninit<Arc<dyn Any + Send + Sync + 'static>>,
+  meta: Meta,
+  size: usize,
 
   prev: *mut Entry,
   next: *mut Entry,
 }
 
 impl Entry {
-  fn new(k: String, v: Arc<(dyn Any + Send + Sync + 'static)>) -> Self {
+  fn new(
+    k: String,
+    v: Arc<dyn Any + Send + Sync + 'static>,
+    meta: Meta,
+    size: usize,
+  ) -> Self {
     Self {
       key: MaybeUninit::new(k),
       val: MaybeUninit::new(v),
+      meta,
+      size,
       prev: ptr::null_mut(),
       next: ptr::null_mut(),
     }
@@ -304,6 +1551,8 @@ impl Entry {
     Self {
       key: MaybeUninit::uninit(),
       val: MaybeUninit::uninit(),
+      meta: Meta::default(),
+      size: 0,
       prev: ptr::null_mut(),
       next: ptr::null_mut(),
     }
@@ -0,0 +1,284 @@
+//! Declarative loading of graphs of linked resources.
+
+use std::any::Any;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::api::par_map;
+use crate::api::Api;
+use crate::api::Endpoint;
+use crate::api::Error;
+use crate::api::Lazy;
+use crate::model::resource::Named;
+use crate::model::NamedResource;
+use crate::model::Resource;
+
+#[cfg(doc)]
+use crate::model::Pokemon;
+#[cfg(doc)]
+use crate::model::Species;
+
+/// The default number of resources an [`Expand`] loads at once.
+const DEFAULT_CONCURRENCY: usize = 8;
+
+/// A loaded resource of some [`Endpoint`] type.
+type Node = Arc<dyn Any + Send + Sync>;
+
+/// A link field of some [`Endpoint`] type, with the type erased.
+type Edge = Box<dyn Fn(&dyn Any) -> Vec<Hop> + Send + Sync>;
+
+/// A link that has yet to be loaded.
+struct Hop {
+  url: String,
+  load: fn(&Api, &str) -> Result<Node, Error>,
+}
+
+/// A link to a resource that can be loaded from an [`Api`].
+///
+/// This is implemented by [`Resource`], [`NamedResource`] and [`Lazy`].
+pub trait Link {
+  /// The type of the resource this link points to.
+  type Target: Endpoint;
+  /// Returns the URL that points to the resource.
+  fn url(&self) -> &str;
+}
+
+impl<T: Endpoint> Link for Lazy<T> {
+  type Target = T;
+  fn url(&self) -> &str {
+    Lazy::url(self)
+  }
+}
+
+impl<T: Endpoint> Link for Resource<T> {
+  type Target = T;
+  fn url(&self) -> &str {
+    Resource::url(self)
+  }
+}
+
+impl<T: Endpoint + Named> Link for NamedResource<T> {
+  type Target = T;
+  fn url(&self) -> &str {
+    NamedResource::url(self)
+  }
+}
+
+/// A description of which links to follow when expanding a resource with
+/// [`Api::expand()`].
+///
+/// Links are chosen per type: every loaded resource of type `S` has the links
+/// registered for `S` followed, up to [`Expand::depth()`] links away from the
+/// root.
+///
+/// ```no_run
+/// # use pkmn::api::Expand;
+/// # use pkmn::model::*;
+/// # fn main() -> Result<(), pkmn::api::Error> {
+/// let api = pkmn::Api::new();
+/// let how = Expand::new()
+///   .depth(2)
+///   .links(|s: &Species| s.varieties.iter().map(|v| &v.pokemon).collect())
+///   .links(|p: &Pokemon| p.types.iter().map(|t| &t.ty).collect())
+///   .links(|p: &Pokemon| p.abilities.iter().map(|a| &a.ability).collect());
+///
+/// let raichu = api.expand(api.by_name::<Species>("raichu")?, &how)?;
+/// for variety in &raichu.varieties {
+///   let pokemon = raichu.get(&variety.pokemon).unwrap();
+///   for ty in raichu.get_all(pokemon.types.iter().map(|t| &t.ty)) {
+///     println!("{}: {}", pokemon.name, ty.name);
+///   }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Expand {
+  edges: HashMap<TypeId, Vec<Edge>>,
+  depth: usize,
+  concurrency: usize,
+}
+
+impl Expand {
+  /// Creates a new [`Expand`] that follows no links.
+  pub fn new() -> Self {
+    Self {
+      edges: HashMap::new(),
+      depth: 1,
+      concurrency: DEFAULT_CONCURRENCY,
+    }
+  }
+
+  /// Sets the maximum number of links between the root and any resource that
+  /// is loaded.
+  ///
+  /// Defaults to 1, which only loads the root's own links.
+  #[inline]
+  pub fn depth(mut self, depth: usize) -> Self {
+    self.depth = depth;
+    self
+  }
+
+  /// Sets the maximum number of resources to load at once.
+  ///
+  /// Defaults to 8.
+  #[inline]
+  pub fn concurrency(mut self, concurrency: usize) -> Self {
+    self.concurrency = concurrency;
+    self
+  }
+
+  /// Follows a single link field of every loaded `S`, such as
+  /// [`Species::growth_rate`].
+  pub fn link<S: Endpoint, L: Link>(
+    self,
+    field: impl Fn(&S) -> &L + Send + Sync + 'static,
+  ) -> Self {
+    self.links(move |s: &S| vec![field(s)])
+  }
+
+  /// Follows a list of links out of every loaded `S`, such as the types of a
+  /// [`Pokemon`].
+  ///
+  /// Optional links can be followed by collecting the [`Option`].
+  pub fn links<S: Endpoint, L: Link>(
+    mut self,
+    field: impl Fn(&S) -> Vec<&L> + Send + Sync + 'static,
+  ) -> Self {
+    let edge = move |node: &dyn Any| {
+      let node = match node.downcast_ref::<S>() {
+        Some(node) => node,
+        None => return Vec::new(),
+      };
+      field(node)
+        .into_iter()
+        .map(|link| Hop {
+          url: link.url().to_string(),
+          load: load_erased::<L::Target>,
+        })
+        .collect()
+    };
+    self
+      .edges
+      .entry(TypeId::of::<S>())
+      .or_default()
+      .push(Box::new(edge));
+    self
+  }
+
+  /// Returns every link out of `node` that should be followed.
+  fn hops(&self, node: &dyn Any) -> Vec<Hop> {
+    let edges = self.edges.get(&node.type_id()).map(Vec::as_slice);
+    edges
+      .unwrap_or_default()
+      .iter()
+      .flat_map(|edge| edge(node))
+      .collect()
+  }
+}
+
+impl Default for Expand {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Loads the `T` at `url`, erasing its type.
+fn load_erased<T: Endpoint>(api: &Api, url: &str) -> Result<Node, Error> {
+  Ok(api.request_json::<T>(url)?)
+}
+
+/// A resource, together with the resources it links to, as loaded by
+/// [`Api::expand()`].
+///
+/// This type dereferences to the root resource; linked resources are looked up
+/// with [`Expanded::get()`].
+#[derive(Clone)]
+pub struct Expanded<T> {
+  root: Arc<T>,
+  loaded: HashMap<String, Node>,
+}
+
+impl<T> Expanded<T> {
+  /// Returns the root resource.
+  pub fn root(&self) -> &Arc<T> {
+    &self.root
+  }
+
+  /// Returns the resource `link` points to, if it was loaded.
+  pub fn get<L: Link>(&self, link: &L) -> Option<Arc<L::Target>> {
+    let node = self.loaded.get(link.url())?;
+    Arc::clone(node).downcast().ok()
+  }
+
+  /// Returns the resources that `links` point to, skipping any that were not
+  /// loaded.
+  pub fn get_all<'a, L: Link + 'a>(
+    &'a self,
+    links: impl IntoIterator<Item = &'a L> + 'a,
+  ) -> impl Iterator<Item = Arc<L::Target>> + 'a {
+    links.into_iter().filter_map(move |link| self.get(link))
+  }
+
+  /// Returns the number of linked resources that were loaded, not counting
+  /// the root.
+  pub fn len(&self) -> usize {
+    self.loaded.len()
+  }
+
+  /// Returns whether no linked resources were loaded.
+  pub fn is_empty(&self) -> bool {
+    self.loaded.is_empty()
+  }
+}
+
+impl<T> Deref for Expanded<T> {
+  type Target = T;
+  fn deref(&self) -> &T {
+    &self.root
+  }
+}
+
+impl Api {
+  /// Loads the resources linked from `root`, as described by `how`.
+  ///
+  /// Links are followed breadth-first: every resource at a particular distance
+  /// from `root` is loaded in parallel, through the cache, before moving on to
+  /// the next. Each resource is only loaded once, even if several links point
+  /// to it.
+  ///
+  /// If any resource fails to load, the first such error is returned.
+  pub fn expand<T: Endpoint>(
+    &self,
+    root: Arc<T>,
+    how: &Expand,
+  ) -> Result<Expanded<T>, Error> {
+    let mut loaded = HashMap::new();
+    let mut seen = HashSet::new();
+    let mut frontier: Vec<Node> = vec![root.clone()];
+
+    for _ in 0..how.depth {
+      let hops = frontier
+        .iter()
+        .flat_map(|node| how.hops(&**node))
+        .filter(|hop| seen.insert(hop.url.clone()))
+        .collect::<Vec<_>>();
+      if hops.is_empty() {
+        break;
+      }
+
+      let results =
+        par_map(&hops, how.concurrency, |hop| (hop.load)(self, &hop.url));
+      frontier.clear();
+      for (hop, result) in hops.into_iter().zip(results) {
+        let node = result?;
+        loaded.insert(hop.url, Arc::clone(&node));
+        frontier.push(node);
+      }
+    }
+
+    Ok(Expanded { root, loaded })
+  }
+}
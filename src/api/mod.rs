@@ -1,12 +1,16 @@
 //! A PokéAPI client.
 
 use std::borrow::Borrow;
+use std::fmt;
 use std::io;
-use std::io::Read;
 use std::marker::PhantomData;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
 use std::sync::Arc;
-
-use reqwest::blocking::Client;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
 
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
@@ -16,8 +20,34 @@ use crate::model::resource::Name;
 use crate::model::resource::Named;
 use crate::model::Resource;
 
+#[cfg(doc)]
+use crate::model::Pokemon;
+
 mod cache;
 pub use cache::Cache;
+pub use cache::CacheEntry;
+pub use cache::CacheStats;
+pub use cache::Compression;
+pub use cache::TierStats;
+
+mod expand;
+pub use expand::Expand;
+pub use expand::Expanded;
+pub use expand::Link;
+
+mod retry;
+pub use retry::Retry;
+use retry::Throttle;
+
+pub mod transport;
+use transport::Conditional;
+use transport::Freshness;
+use transport::Transport;
+
+#[cfg(feature = "async")]
+pub mod nonblocking;
+#[cfg(feature = "async")]
+pub use nonblocking::AsyncApi;
 
 /// An API client.
 ///
@@ -25,8 +55,10 @@ pub use cache::Cache;
 /// Requests are memoized using a hybrid memory/disk [`Cache`].
 pub struct Api {
   base_url: String,
-  cache: Cache,
-  client: Client,
+  cache: Arc<Cache>,
+  transport: Box<dyn Transport>,
+  retry: Retry,
+  throttle: Throttle,
 }
 
 /// Options for constructing an [`Api`].
@@ -34,19 +66,62 @@ pub struct Options {
   /// The base URL to point the client at.
   pub base_url: String,
   /// The cache to use with the client.
-  pub cache: Cache,
+  ///
+  /// The same cache may be shared between several clients, including an
+  /// `AsyncApi`.
+  pub cache: Arc<Cache>,
+  /// The transport used to perform requests.
+  ///
+  /// When this is a [`transport::Http`], an `AsyncApi` makes requests with
+  /// its asynchronous client rather than its blocking one; see
+  /// `transport::Http::with_async_client()`. Any other transport is called
+  /// directly from the `AsyncApi`'s futures, blocking them while it runs, so it
+  /// should not perform network requests of its own.
+  pub transport: Box<dyn Transport>,
+  /// The policy for retrying failed requests.
+  pub retry: Retry,
+  /// The maximum number of requests to make per second, if any.
+  ///
+  /// Requests served from the cache do not count towards this limit.
+  pub requests_per_second: Option<f64>,
+}
+
+impl Default for Options {
+  fn default() -> Self {
+    Self {
+      base_url: DEFAULT_BASE_URL.to_string(),
+      cache: Arc::new(Cache::new(128)),
+      transport: Box::new(transport::Http::new()),
+      retry: Retry::default(),
+      requests_per_second: None,
+    }
+  }
 }
 
+/// The default PokéAPI URL.
+const DEFAULT_BASE_URL: &str = "https://pokeapi.co/api/v2";
+
+/// The page size [`Api::prewarm()`] uses for walking listings.
+const PREWARM_PAGE_SIZE: usize = 100;
+
+/// The number of threads [`Api::prewarm()`] loads resources with.
+const PREWARM_THREADS: usize = 8;
+
 /// An [`Api`] client error.
 ///
 /// See [`Error`].
 #[derive(Debug, thiserror::Error)]
-#[error("failed to GET `{url}`: {kind}")]
+#[error("{}: {kind}", describe(.url, *.attempts))]
 pub struct Error {
   /// The URL whose fetch generated this error.
   pub url: String,
   /// The kind of error produced.
   pub kind: ErrorKind,
+  /// The number of times the request was attempted before giving up.
+  ///
+  /// This is zero for errors that did not come from a failed request, such as
+  /// a response or cache entry that could not be deserialized.
+  pub attempts: u32,
 }
 
 impl Error {
@@ -54,10 +129,19 @@ impl Error {
     Self {
       url: url.into(),
       kind: kind.into(),
+      attempts: 0,
     }
   }
 }
 
+/// Describes what failed, for [`Error`]'s `Display` impl.
+fn describe(url: &str, attempts: u32) -> String {
+  match attempts {
+    0 => format!("failed to load `{}`", url),
+    n => format!("failed to GET `{}` after {} attempt(s)", url, n),
+  }
+}
+
 /// An [`Api`] client error kind.
 ///
 /// See [`Error`].
@@ -72,6 +156,60 @@ pub enum ErrorKind {
 
   #[error(transparent)]
   Json(#[from] serde_json::Error),
+
+  /// The requested resource does not exist.
+  #[error("resource not found (HTTP {0})")]
+  NotFound(u16),
+
+  /// The server is refusing requests because too many have been made.
+  #[error("rate limited (HTTP {status})")]
+  RateLimited {
+    status: u16,
+    /// How long the server asked to wait before trying again, if it did.
+    retry_after: Option<Duration>,
+  },
+
+  /// The server failed to process the request.
+  #[error("server error (HTTP {0})")]
+  ServerError(u16),
+
+  /// The server responded with some other unsuccessful status.
+  #[error("unexpected status (HTTP {0})")]
+  Status(u16),
+
+  /// The URL passed to [`Api::by_url()`] does not point to a resource of the
+  /// expected endpoint, which is given.
+  #[error("not the URL of a `{0}` resource")]
+  WrongEndpoint(&'static str),
+}
+
+impl Error {
+  /// Returns whether this error indicates that the requested resource does not
+  /// exist.
+  pub fn is_not_found(&self) -> bool {
+    matches!(self.kind, ErrorKind::NotFound(_))
+  }
+}
+
+impl ErrorKind {
+  /// Returns the HTTP status code of the response that caused this error, if
+  /// there was one.
+  pub fn status(&self) -> Option<u16> {
+    match self {
+      Self::NotFound(status)
+      | Self::RateLimited { status, .. }
+      | Self::ServerError(status)
+      | Self::Status(status) => Some(*status),
+      Self::Http(e) => e.status().map(|s| s.as_u16()),
+      _ => None,
+    }
+  }
+}
+
+impl Default for Api {
+  fn default() -> Self {
+    Self::new()
+  }
 }
 
 impl Api {
@@ -83,8 +221,18 @@ impl Api {
   /// Creates a new [`Api`] with the given cache.
   pub fn with_cache(cache: Cache) -> Self {
     Self::with_options(Options {
-      base_url: "https://pokeapi.co/api/v2".to_string(),
-      cache,
+      cache: Arc::new(cache),
+      ..Options::default()
+    })
+  }
+
+  /// Creates a new [`Api`] with the given transport.
+  ///
+  /// The returned [`Api`] will use the default cache and URL.
+  pub fn with_transport(transport: impl Transport + 'static) -> Self {
+    Self::with_options(Options {
+      transport: Box::new(transport),
+      ..Options::default()
     })
   }
 
@@ -93,26 +241,57 @@ impl Api {
     Self {
       base_url: opts.base_url,
       cache: opts.cache,
-      client: Client::new(),
+      transport: opts.transport,
+      retry: opts.retry,
+      throttle: Throttle::new(opts.requests_per_second),
+    }
+  }
+
+  /// Returns the cache backing this client.
+  pub fn cache(&self) -> &Arc<Cache> {
+    &self.cache
+  }
+
+  /// Performs an uncached GET request, retrying it as necessary.
+  ///
+  /// If `cached` is present, the request is conditional on the resource having
+  /// changed since it was cached.
+  fn fetch(
+    &self,
+    url: &str,
+    cached: Option<&Freshness>,
+  ) -> Result<Conditional, Error> {
+    let mut attempts = 0;
+    loop {
+      attempts += 1;
+      thread::sleep(self.throttle.reserve());
+
+      let e = match self.transport.get_conditional(url, cached) {
+        Ok(response) => return Ok(response),
+        Err(e) => e,
+      };
+      match self.retry.backoff(attempts, &e) {
+        Some(backoff) => thread::sleep(backoff),
+        None => {
+          return Err(Error {
+            attempts,
+            ..Error::new(url, e)
+          })
+        }
+      }
     }
   }
 
   /// Base request-generating function, with caching.
   fn request_blob(&self, url: &str) -> Result<Arc<Box<[u8]>>, Error> {
-    let client = &self.client;
     self.cache.get(
       url,
       |buf| Ok(buf.into_boxed_slice()),
       |val| Ok(val.clone().into()),
-      || {
-        let mut buf = Vec::new();
-        client
-          .get(url)
-          .send()
-          .map_err(|e| Error::new(url, e))?
-          .read_to_end(&mut buf)
-          .map_err(|e| Error::new(url, e))?;
-        Ok(buf.into_boxed_slice())
+      |cached| {
+        self
+          .fetch(url, cached)?
+          .try_map(|buf| Ok(buf.into_boxed_slice()))
       },
     )
   }
@@ -122,24 +301,49 @@ impl Api {
     &self,
     url: &str,
   ) -> Result<Arc<T>, Error> {
-    let client = &self.client;
-    self.cache.get(
+    self.request_json_with(url, None)
+  }
+
+  /// Like [`Api::request_json()`], but uses `prefetched` instead of making a
+  /// request, if one turns out to be necessary.
+  ///
+  /// If a resource is fetched by ID, its URL by name is remembered as an alias
+  /// while it is in memory, so that [`Api::by_name()`] can find it without a
+  /// request. Unlike the aliases [`Api::by_name()`] records, these are not
+  /// written to disk, which would double the number of files there.
+  fn request_json_with<T>(
+    &self,
+    url: &str,
+    prefetched: Option<Conditional>,
+  ) -> Result<Arc<T>, Error>
+  where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+  {
+    let mut name_url = None;
+    let val = self.cache.get(
       url,
       |buf| {
         serde_json::from_reader(&mut &buf[..]).map_err(|e| Error::new(url, e))
       },
       |val| serde_json::to_vec(val).map_err(|e| Error::new(url, e)),
-      || {
-        let mut buf = Vec::new();
-        client
-          .get(url)
-          .send()
-          .map_err(|e| Error::new(url, e))?
-          .read_to_end(&mut buf)
-          .map_err(|e| Error::new(url, e))?;
-        serde_json::from_reader(&mut &buf[..]).map_err(|e| Error::new(url, e))
+      |cached| {
+        let response = match prefetched {
+          Some(response) => response,
+          None => {
+            let response = self.fetch(url, cached)?;
+            name_url = name_url_of(&self.base_url, url, &response);
+            response
+          }
+        };
+        response.try_map(|buf| {
+          serde_json::from_reader(&mut &buf[..]).map_err(|e| Error::new(url, e))
+        })
       },
-    )
+    )?;
+    if let Some(name_url) = name_url {
+      self.cache.remember_alias(&name_url, url);
+    }
+    Ok(val)
   }
 
   /// Returns an iterator over all resources of a particular type.
@@ -149,33 +353,152 @@ impl Api {
   pub fn all<T: Endpoint>(
     &self,
     per_page: usize,
+  ) -> impl Iterator<Item = Result<Arc<T>, Error>> + '_ {
+    self.walk(per_page, false)
+  }
+
+  /// Returns an iterator over all resources of a particular type, in reverse
+  /// order.
+  ///
+  /// See [`Api::all()`].
+  pub fn all_rev<T: Endpoint>(
+    &self,
+    per_page: usize,
+  ) -> impl Iterator<Item = Result<Arc<T>, Error>> + '_ {
+    self.walk(per_page, true)
+  }
+
+  /// Walks a [`Listing`] forwards or backwards, loading each resource in it.
+  fn walk<T: Endpoint>(
+    &self,
+    per_page: usize,
+    reverse: bool,
   ) -> impl Iterator<Item = Result<Arc<T>, Error>> + '_ {
     let mut listing = Listing::<T, _>::new(self, per_page);
     let mut results: Option<ListingResults<T>> = None;
     let mut result_idx = 0;
-    let mut had_err = false;
-    std::iter::from_fn(move || {
-      if had_err {
+    let mut done = false;
+    std::iter::from_fn(move || loop {
+      if done {
         return None;
       }
 
-      if results.is_none() || results.as_ref().unwrap().len() >= result_idx {
-        results = match listing.advance() {
-          Ok(results) => {
-            result_idx = 0;
-            Some(results?)
-          }
+      if let Some(results) = results.as_ref().filter(|r| result_idx < r.len()) {
+        let idx = if reverse {
+          results.len() - 1 - result_idx
+        } else {
+          result_idx
+        };
+        result_idx += 1;
+        return Some(results[idx].load(self));
+      }
+
+      let next = if reverse {
+        listing.retreat()
+      } else {
+        listing.advance()
+      };
+      match next {
+        Ok(Some(next)) => {
+          results = Some(next);
+          result_idx = 0;
+        }
+        Ok(None) => done = true,
+        Err(e) => {
+          done = true;
+          return Some(Err(e));
+        }
+      }
+    })
+  }
+
+  /// Loads every resource of type `T` into the cache, returning how many there
+  /// were.
+  ///
+  /// The listing of resources is walked one page at a time, and the resources
+  /// on each page are loaded in parallel. This is useful for filling a cache
+  /// ahead of time, so that it can be shipped somewhere else.
+  pub fn prewarm<T: Endpoint>(&self) -> Result<usize, Error> {
+    let mut listing = self.listing_of::<T>(PREWARM_PAGE_SIZE);
+    let mut count = 0;
+    while let Some(results) = listing.advance()? {
+      for result in results.load_all(self, PREWARM_THREADS, &()) {
+        result?;
+      }
+      count += results.len();
+    }
+    Ok(count)
+  }
+
+  /// Returns an iterator over all resources of a particular type, which are
+  /// loaded in the background, up to `concurrency` at a time.
+  ///
+  /// Unlike [`Api::all()`], resources are yielded as soon as they finish
+  /// loading, which may not be the order in which they are listed. Errors
+  /// loading individual resources do not stop the iteration, but errors
+  /// loading a page of the listing do.
+  ///
+  /// `progress` is told the total number of resources once the first page of
+  /// the listing is loaded, and about every resource that finishes loading.
+  /// Dropping the iterator stops loading more resources.
+  pub fn all_parallel<T: Endpoint>(
+    self: &Arc<Self>,
+    per_page: usize,
+    concurrency: usize,
+    progress: impl ReportProgress + 'static,
+  ) -> impl Iterator<Item = Result<Arc<T>, Error>> {
+    let progress = Arc::new(progress);
+    let (work_sink, work) = mpsc::sync_channel::<Resource<T>>(per_page);
+    let (result_sink, results) = mpsc::channel();
+
+    // Workers stop once the iterator is dropped, which in turn causes the
+    // listing thread to stop once all of the workers have.
+    let work = Arc::new(Mutex::new(work));
+    for _ in 0..concurrency.max(1) {
+      let api = Arc::clone(self);
+      let work = Arc::clone(&work);
+      let result_sink = result_sink.clone();
+      let progress = Arc::clone(&progress);
+      thread::spawn(move || loop {
+        let resource = match work.lock().unwrap().recv() {
+          Ok(resource) => resource,
+          Err(_) => return,
+        };
+        let result = resource.load(&api);
+        progress.add_completed(1);
+        if result_sink.send(result).is_err() {
+          return;
+        }
+      });
+    }
+
+    let api = Arc::clone(self);
+    thread::spawn(move || {
+      let mut listing = Listing::<T, _>::new(&*api, per_page);
+      let mut first = true;
+      loop {
+        let page = match listing.advance() {
+          Ok(Some(page)) => page,
+          Ok(None) => return,
           Err(e) => {
-            had_err = true;
-            return Some(Err(e));
+            let _ = result_sink.send(Err(e));
+            return;
+          }
+        };
+        if first {
+          progress.add_total(listing.estimate_len().unwrap_or(page.len()));
+          first = false;
+        }
+
+        for resource in page.iter() {
+          if work_sink.send(resource.clone()).is_err() {
+            return;
           }
         }
       }
+    });
 
-      let r = &results.as_ref().unwrap()[result_idx];
-      result_idx += 1;
-      Some(r.load(self))
-    })
+    results.into_iter()
   }
 
   /// Returns a [`Listing`] that borrows `self`.
@@ -184,8 +507,48 @@ impl Api {
   }
 
   /// Try to get the specific resource of type `T` with the given name.
+  ///
+  /// Resources are cached by ID, so the same resource requested by name and
+  /// by ID (or through a [`Resource`]) is only stored once. `name` may also be
+  /// a numeric ID.
   pub fn by_name<T: Endpoint>(&self, name: &str) -> Result<Arc<T>, Error> {
-    self.request_json(&format!("{}/{}/{}", self.base_url, T::NAME, name))
+    if let Ok(id) = name.parse() {
+      return self.by_id(id);
+    }
+
+    let name_url = resource_url::<T>(&self.base_url, name);
+    if let Some(url) = self.cache.resolve(&name_url) {
+      let val = self.request_json(&url)?;
+      self.cache.remember_alias(&name_url, &url);
+      return Ok(val);
+    }
+
+    // We don't know this resource's ID yet, so we need to request it by name
+    // and file it under its ID, if it has one.
+    let response = self.fetch(&name_url, None)?;
+    let url = match id_of(&response) {
+      Some(id) => resource_url::<T>(&self.base_url, id),
+      None => return self.request_json_with(&name_url, Some(response)),
+    };
+    let val = self.request_json_with(&url, Some(response))?;
+    self.cache.alias(&name_url, &url);
+    Ok(val)
+  }
+
+  /// Try to get the specific resource of type `T` with the given ID.
+  pub fn by_id<T: Endpoint>(&self, id: u32) -> Result<Arc<T>, Error> {
+    self.request_json(&resource_url::<T>(&self.base_url, id))
+  }
+
+  /// Try to get the specific resource of type `T` at the given URL.
+  ///
+  /// `url` must be the URL of a resource of type `T`, such as
+  /// `https://pokeapi.co/api/v2/pokemon/25/` for a [`Pokemon`]; otherwise, an
+  /// [`ErrorKind::WrongEndpoint`] is returned.
+  pub fn by_url<T: Endpoint>(&self, url: &str) -> Result<Arc<T>, Error> {
+    let name = resource_name::<T>(&self.base_url, url)
+      .ok_or_else(|| Error::new(url, ErrorKind::WrongEndpoint(T::NAME)))?;
+    self.by_name::<T>(name)
   }
 
   /// Try to get the specific resource of type `T` with the given variant.
@@ -206,6 +569,45 @@ pub trait Endpoint:
   const NAME: &'static str;
 }
 
+/// A sink for progress reports from long-running operations, such as
+/// [`Api::all_parallel()`].
+///
+/// Progress is measured in abstract units of work, such as resources to load.
+/// `()` can be used to ignore progress reports.
+pub trait ReportProgress: Send + Sync {
+  /// Called when `n` more units of work are discovered.
+  fn add_total(&self, n: usize) {
+    let _ = n;
+  }
+
+  /// Called when `n` more units of work are completed.
+  fn add_completed(&self, n: usize) {
+    let _ = n;
+  }
+}
+
+impl ReportProgress for () {}
+
+impl<P: ReportProgress + ?Sized> ReportProgress for &P {
+  fn add_total(&self, n: usize) {
+    P::add_total(self, n)
+  }
+
+  fn add_completed(&self, n: usize) {
+    P::add_completed(self, n)
+  }
+}
+
+impl<P: ReportProgress + ?Sized> ReportProgress for Arc<P> {
+  fn add_total(&self, n: usize) {
+    P::add_total(self, n)
+  }
+
+  fn add_completed(&self, n: usize) {
+    P::add_completed(self, n)
+  }
+}
+
 /// A lazy listing over all resources of type `T`.
 ///
 /// This type will work through PokeAPI's listings of all resources of a
@@ -217,6 +619,7 @@ pub trait Endpoint:
 pub struct Listing<T, A> {
   api: A,
   page: Option<Arc<Page<T>>>,
+  offset: usize,
   per_page: usize,
 }
 
@@ -227,6 +630,124 @@ struct Page<T> {
   count: u32,
 }
 
+/// Returns the URL for the resource of type `T` with the given name or ID.
+fn resource_url<T: Endpoint>(
+  base_url: &str,
+  name: impl fmt::Display,
+) -> String {
+  format!("{}/{}/{}/", base_url, T::NAME, name)
+}
+
+/// Returns the name or ID in `url`, if it is the URL of a resource of type
+/// `T`.
+fn resource_name<'a, T: Endpoint>(
+  base_url: &str,
+  url: &'a str,
+) -> Option<&'a str> {
+  let name = url
+    .strip_prefix(base_url)?
+    .strip_prefix('/')?
+    .strip_prefix(T::NAME)?
+    .strip_prefix('/')?;
+  let name = name.strip_suffix('/').unwrap_or(name);
+  Some(name).filter(|n| !n.is_empty() && !n.contains(['/', '?']))
+}
+
+/// Returns the ID of the resource in a response, if it has one.
+fn id_of(response: &Conditional) -> Option<u32> {
+  #[derive(Deserialize)]
+  struct Id {
+    id: u32,
+  }
+
+  match response {
+    Conditional::Modified(buf, _) => {
+      serde_json::from_slice::<Id>(buf).ok().map(|r| r.id)
+    }
+    Conditional::NotModified(_) => None,
+  }
+}
+
+/// Returns the URL by name of the resource in a response to a request for
+/// `url`, if `url` is the URL by ID of a resource with a name.
+fn name_url_of(
+  base_url: &str,
+  url: &str,
+  response: &Conditional,
+) -> Option<String> {
+  #[derive(Deserialize)]
+  struct Name {
+    name: String,
+  }
+
+  let path = url.strip_prefix(base_url)?.strip_prefix('/')?;
+  let (endpoint, id) = path.strip_suffix('/')?.split_once('/')?;
+  id.parse::<u32>().ok()?;
+
+  let name = match response {
+    Conditional::Modified(buf, _) => {
+      serde_json::from_slice::<Name>(buf).ok()?
+    }
+    Conditional::NotModified(_) => return None,
+  };
+  Some(name.name)
+    .filter(|n| !n.is_empty() && n.parse::<u32>().is_err())
+    .filter(|n| !n.contains(['/', '?']))
+    .map(|n| format!("{}/{}/{}/", base_url, endpoint, n))
+}
+
+/// Applies `f` to every element of `items` on up to `concurrency` threads,
+/// returning the results in the same order.
+fn par_map<I: Sync, R: Send>(
+  items: &[I],
+  concurrency: usize,
+  f: impl Fn(&I) -> R + Sync,
+) -> Vec<R> {
+  let next = AtomicUsize::new(0);
+  let mut done = thread::scope(|s| {
+    let workers = (0..concurrency.max(1).min(items.len()))
+      .map(|_| {
+        s.spawn(|| {
+          let mut done = Vec::new();
+          loop {
+            let idx = next.fetch_add(1, Ordering::Relaxed);
+            let item = match items.get(idx) {
+              Some(item) => item,
+              None => return done,
+            };
+            done.push((idx, f(item)));
+          }
+        })
+      })
+      .collect::<Vec<_>>();
+    workers
+      .into_iter()
+      .flat_map(|w| w.join().unwrap())
+      .collect::<Vec<_>>()
+  });
+
+  done.sort_by_key(|&(idx, _)| idx);
+  done.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Returns the URL for the page of a listing of `T` starting at `offset`.
+fn listing_url<T: Endpoint>(
+  base_url: &str,
+  offset: usize,
+  per_page: usize,
+) -> String {
+  if offset == 0 {
+    return format!("{}/{}?limit={}", base_url, T::NAME, per_page);
+  }
+  format!(
+    "{}/{}?offset={}&limit={}",
+    base_url,
+    T::NAME,
+    offset,
+    per_page
+  )
+}
+
 /// Results from a [`Listing`] operation.
 ///
 /// This type may be cheaply cloned, since it is reference-counted under the
@@ -236,6 +757,27 @@ pub struct ListingResults<T> {
   page: Arc<Page<T>>,
 }
 
+impl<T: Endpoint> ListingResults<T> {
+  /// Loads every resource in these results, up to `concurrency` at a time,
+  /// returning them in the same order.
+  ///
+  /// `progress` is told the total number of resources up front, and about
+  /// every resource that finishes loading.
+  pub fn load_all(
+    &self,
+    api: &Api,
+    concurrency: usize,
+    progress: &dyn ReportProgress,
+  ) -> Vec<Result<Arc<T>, Error>> {
+    progress.add_total(self.len());
+    par_map(self, concurrency, |resource| {
+      let result = resource.load(api);
+      progress.add_completed(1);
+      result
+    })
+  }
+}
+
 impl<T> std::ops::Deref for ListingResults<T> {
   type Target = [Resource<T>];
   fn deref(&self) -> &[Resource<T>] {
@@ -252,37 +794,112 @@ impl<T: Endpoint, A: Borrow<Api>> Listing<T, A> {
     Self {
       api,
       page: None,
+      offset: 0,
       per_page,
     }
   }
 
   /// Drives this listing forward by requesting the next page in the listing.
   pub fn advance(&mut self) -> Result<Option<ListingResults<T>>, Error> {
-    let url;
-    let next = match self.page.as_ref() {
-      Some(page) => match page.next.as_ref() {
-        Some(next) => next,
-        None => return Ok(None),
-      },
-      None => {
-        url = format!(
-          "{}/{}?limit={}",
-          self.api.borrow().base_url,
-          T::NAME,
-          self.per_page
-        );
-        &url
+    match self.page.as_ref() {
+      Some(page) if page.next.is_none() => Ok(None),
+      Some(page) => self.seek(self.offset + page.results.len()),
+      None => self.seek(0),
+    }
+  }
+
+  /// Drives this listing backward by requesting the previous page in the
+  /// listing.
+  ///
+  /// If no page has been requested yet, this requests the last page, so that
+  /// calling this function repeatedly walks the listing from the end.
+  pub fn retreat(&mut self) -> Result<Option<ListingResults<T>>, Error> {
+    if self.page.is_some() {
+      if self.offset == 0 {
+        return Ok(None);
       }
-    };
+      // After an unaligned seek, the previous page may be a short one.
+      let limit = self.per_page.min(self.offset);
+      return self.seek_with_limit(self.offset - limit, limit);
+    }
+
+    match self.len()? {
+      0 => Ok(None),
+      len => self.page_at((len - 1) / self.per_page.max(1)),
+    }
+  }
+
+  /// Moves this listing to the page starting at the `offset`th resource,
+  /// returning its results.
+  ///
+  /// Returns `None` if `offset` is past the end of the listing.
+  pub fn seek(
+    &mut self,
+    offset: usize,
+  ) -> Result<Option<ListingResults<T>>, Error> {
+    self.seek_with_limit(offset, self.per_page)
+  }
+
+  /// Like [`Listing::seek()`], but requests at most `limit` resources.
+  fn seek_with_limit(
+    &mut self,
+    offset: usize,
+    limit: usize,
+  ) -> Result<Option<ListingResults<T>>, Error> {
+    let api = self.api.borrow();
+    let url = listing_url::<T>(&api.base_url, offset, limit);
+    let page = api.request_json::<Page<_>>(&url)?;
+    if page.results.is_empty() && offset > 0 {
+      return Ok(None);
+    }
 
-    self.page = Some(self.api.borrow().request_json::<Page<_>>(next)?);
+    self.page = Some(page);
+    self.offset = offset;
     Ok(self.current_results())
   }
 
+  /// Moves this listing to the `n`th page, counting from zero, returning its
+  /// results.
+  ///
+  /// Returns `None` if there is no such page.
+  pub fn page_at(
+    &mut self,
+    n: usize,
+  ) -> Result<Option<ListingResults<T>>, Error> {
+    self.seek(n.saturating_mul(self.per_page))
+  }
+
+  /// Returns the offset of the first resource in the current page, if a page
+  /// has been requested.
+  pub fn offset(&self) -> Option<usize> {
+    self.page.as_ref().map(|_| self.offset)
+  }
+
+  /// Returns the total number of resources in this listing.
+  ///
+  /// If no page has been requested yet, this requests the first page (without
+  /// moving the listing to it).
+  pub fn len(&self) -> Result<usize, Error> {
+    if let Some(page) = &self.page {
+      return Ok(page.count as usize);
+    }
+
+    let api = self.api.borrow();
+    let url = listing_url::<T>(&api.base_url, 0, self.per_page);
+    Ok(api.request_json::<Page<T>>(&url)?.count as usize)
+  }
+
+  /// Returns whether this listing is empty.
+  ///
+  /// See [`Listing::len()`].
+  pub fn is_empty(&self) -> Result<bool, Error> {
+    Ok(self.len()? == 0)
+  }
+
   /// Returns a copy of the results for the current page.
   pub fn current_results(&self) -> Option<ListingResults<T>> {
     self.page.as_ref().map(|p| ListingResults {
-      page: Arc::clone(&p),
+      page: Arc::clone(p),
     })
   }
 
@@ -318,6 +935,15 @@ impl Blob {
   pub fn load(&self, api: &Api) -> Result<Arc<Box<[u8]>>, Error> {
     api.request_blob(&self.url)
   }
+
+  /// Performs an asynchronous network request to lazily evaluate this blob.
+  #[cfg(feature = "async")]
+  pub async fn load_async(
+    &self,
+    api: &AsyncApi,
+  ) -> Result<Arc<Box<[u8]>>, Error> {
+    api.request_blob(&self.url).await
+  }
 }
 
 /// A lazily-loaded object.
@@ -353,4 +979,10 @@ impl<T: Serialize + DeserializeOwned + Send + Sync + 'static> Lazy<T> {
   pub fn load(&self, api: &Api) -> Result<Arc<T>, Error> {
     api.request_json(&self.url)
   }
+
+  /// Performs an asynchronous network request to lazily evaluate this object.
+  #[cfg(feature = "async")]
+  pub async fn load_async(&self, api: &AsyncApi) -> Result<Arc<T>, Error> {
+    api.request_json(&self.url).await
+  }
 }
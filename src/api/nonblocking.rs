@@ -0,0 +1,491 @@
+//! An asynchronous PokéAPI client.
+//!
+//! This module is only available with the `async` feature enabled. Its types
+//! mirror the blocking ones in [`crate::api`], and can share a [`Cache`] with
+//! them. Lazily-loaded values, such as [`Blob`], [`Lazy`] and [`Resource`],
+//! can be loaded through an [`AsyncApi`] with their `load_async()` methods.
+
+use std::any::Any;
+use std::borrow::Borrow;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::stream;
+use futures::Stream;
+
+use reqwest::Client;
+use reqwest::StatusCode;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::api::id_of;
+use crate::api::listing_url;
+use crate::api::name_url_of;
+use crate::api::resource_name;
+use crate::api::resource_url;
+use crate::api::transport;
+use crate::api::transport::Conditional;
+use crate::api::transport::Freshness;
+use crate::api::transport::Http;
+use crate::api::transport::Transport;
+use crate::api::Cache;
+use crate::api::Endpoint;
+use crate::api::Error;
+use crate::api::ErrorKind;
+use crate::api::ListingResults;
+use crate::api::Options;
+use crate::api::Page;
+use crate::api::Retry;
+use crate::api::Throttle;
+use crate::api::DEFAULT_BASE_URL;
+use crate::model::resource::Name;
+use crate::model::resource::Named;
+
+#[cfg(doc)]
+use crate::api::Api;
+#[cfg(doc)]
+use crate::api::Blob;
+#[cfg(doc)]
+use crate::api::Lazy;
+#[cfg(doc)]
+use crate::api::Listing;
+#[cfg(doc)]
+use crate::model::Resource;
+
+/// An asynchronous API client.
+///
+/// This type is the asynchronous counterpart to [`Api`]. Requests are memoized
+/// using a hybrid memory/disk [`Cache`], which may be shared with an [`Api`].
+pub struct AsyncApi {
+  base_url: String,
+  cache: Arc<Cache>,
+  client: Client,
+  /// A transport to use instead of `client`; see [`Options::transport`].
+  transport: Option<Box<dyn Transport>>,
+  retry: Retry,
+  throttle: Throttle,
+}
+
+impl Default for AsyncApi {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl AsyncApi {
+  /// Creates a new [`AsyncApi`] with the default cache and URL.
+  pub fn new() -> Self {
+    Self::with_cache(Cache::new(128))
+  }
+
+  /// Creates a new [`AsyncApi`] with the given cache.
+  pub fn with_cache(cache: Cache) -> Self {
+    Self {
+      base_url: DEFAULT_BASE_URL.to_string(),
+      cache: Arc::new(cache),
+      client: Client::new(),
+      transport: None,
+      retry: Retry::default(),
+      throttle: Throttle::new(None),
+    }
+  }
+
+  /// Creates a new [`AsyncApi`] with the given options.
+  ///
+  /// See [`Options::transport`] for how the transport is used.
+  pub fn with_options(opts: Options) -> Self {
+    let transport = opts.transport;
+    let http = (&*transport as &dyn Any).downcast_ref::<Http>();
+    let client = http.map(|h| h.async_client().clone());
+    Self {
+      base_url: opts.base_url,
+      cache: opts.cache,
+      transport: if client.is_some() {
+        None
+      } else {
+        Some(transport)
+      },
+      client: client.unwrap_or_default(),
+      retry: opts.retry,
+      throttle: Throttle::new(opts.requests_per_second),
+    }
+  }
+
+  /// Returns the cache backing this client.
+  pub fn cache(&self) -> &Arc<Cache> {
+    &self.cache
+  }
+
+  /// Performs an uncached GET request, retrying it as necessary.
+  ///
+  /// If `cached` is present, the request is conditional on the resource having
+  /// changed since it was cached.
+  async fn fetch(
+    &self,
+    url: &str,
+    cached: Option<Freshness>,
+  ) -> Result<Conditional, Error> {
+    let mut attempts = 0;
+    loop {
+      attempts += 1;
+      let wait = self.throttle.reserve();
+      if wait > Duration::default() {
+        tokio::time::sleep(wait).await;
+      }
+
+      let e = match self.fetch_once(url, cached.as_ref()).await {
+        Ok(response) => return Ok(response),
+        Err(e) => e,
+      };
+      match self.retry.backoff(attempts, &e) {
+        Some(backoff) => tokio::time::sleep(backoff).await,
+        None => {
+          return Err(Error {
+            attempts,
+            ..Error::new(url, e)
+          })
+        }
+      }
+    }
+  }
+
+  /// Performs a single GET request.
+  async fn fetch_once(
+    &self,
+    url: &str,
+    cached: Option<&Freshness>,
+  ) -> Result<Conditional, ErrorKind> {
+    if let Some(transport) = &self.transport {
+      return transport.get_conditional(url, cached);
+    }
+
+    let mut request = self.client.get(url);
+    if let Some(cached) = cached {
+      request = request.headers(cached.to_headers());
+    }
+
+    let response = request.send().await?;
+    let freshness = Freshness::from_headers(response.headers());
+    if cached.is_some() && response.status() == StatusCode::NOT_MODIFIED {
+      return Ok(Conditional::NotModified(freshness));
+    }
+    transport::check_status(response.status(), response.headers())?;
+
+    let bytes = response.bytes().await?;
+    Ok(Conditional::Modified(bytes.to_vec(), freshness))
+  }
+
+  /// Base request-generating function, with caching.
+  pub(in crate::api) async fn request_blob(
+    &self,
+    url: &str,
+  ) -> Result<Arc<Box<[u8]>>, Error> {
+    self
+      .cache
+      .get_async(
+        url,
+        |buf| Ok(buf.into_boxed_slice()),
+        |val| Ok(val.clone().into()),
+        |cached| async move {
+          self
+            .fetch(url, cached)
+            .await?
+            .try_map(|buf| Ok(buf.into_boxed_slice()))
+        },
+      )
+      .await
+  }
+
+  /// Base request-generating function, with caching.
+  pub(in crate::api) async fn request_json<T>(
+    &self,
+    url: &str,
+  ) -> Result<Arc<T>, Error>
+  where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+  {
+    self.request_json_with(url, None).await
+  }
+
+  /// Like [`AsyncApi::request_json()`], but uses `prefetched` instead of
+  /// making a request, if one turns out to be necessary.
+  ///
+  /// See [`Api::request_json_with()`].
+  async fn request_json_with<T>(
+    &self,
+    url: &str,
+    prefetched: Option<Conditional>,
+  ) -> Result<Arc<T>, Error>
+  where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+  {
+    let mut name_url = None;
+    let learned = &mut name_url;
+    let val = self
+      .cache
+      .get_async(
+        url,
+        |buf| {
+          serde_json::from_reader(&mut &buf[..]).map_err(|e| Error::new(url, e))
+        },
+        |val| serde_json::to_vec(val).map_err(|e| Error::new(url, e)),
+        |cached| async move {
+          let response = match prefetched {
+            Some(response) => response,
+            None => {
+              let response = self.fetch(url, cached).await?;
+              *learned = name_url_of(&self.base_url, url, &response);
+              response
+            }
+          };
+          response.try_map(|buf| {
+            serde_json::from_reader(&mut &buf[..])
+              .map_err(|e| Error::new(url, e))
+          })
+        },
+      )
+      .await?;
+    if let Some(name_url) = name_url {
+      self.cache.remember_alias(&name_url, url);
+    }
+    Ok(val)
+  }
+
+  /// Returns a stream over all resources of a particular type.
+  ///
+  /// Like [`Api::all()`], a resource that fails to load is yielded as an error
+  /// and skipped, but the stream ends after failing to load a page of the
+  /// listing. If fine-grained control of how network requests are done is
+  /// needed, consider using the [`AsyncListing`] type instead.
+  pub fn all<T: Endpoint>(
+    &self,
+    per_page: usize,
+  ) -> impl Stream<Item = Result<Arc<T>, Error>> + '_ {
+    let listing = AsyncListing::<T, _>::new(self, per_page);
+    stream::unfold(
+      Some((listing, None::<ListingResults<T>>, 0)),
+      move |state| async move {
+        let (mut listing, mut results, mut result_idx) = state?;
+        loop {
+          let next = results.as_ref().and_then(|r| r.get(result_idx)).cloned();
+          if let Some(next) = next {
+            let val = next.load_async(self).await;
+            return Some((val, Some((listing, results, result_idx + 1))));
+          }
+
+          match listing.advance().await {
+            Ok(Some(next)) => {
+              results = Some(next);
+              result_idx = 0;
+            }
+            Ok(None) => return None,
+            Err(e) => return Some((Err(e), None)),
+          }
+        }
+      },
+    )
+  }
+
+  /// Returns an [`AsyncListing`] that borrows `self`.
+  pub fn listing_of<T: Endpoint>(
+    &self,
+    per_page: usize,
+  ) -> AsyncListing<T, &Self> {
+    AsyncListing::new(self, per_page)
+  }
+
+  /// Try to get the specific resource of type `T` with the given name.
+  ///
+  /// See [`Api::by_name()`].
+  pub async fn by_name<T: Endpoint>(
+    &self,
+    name: &str,
+  ) -> Result<Arc<T>, Error> {
+    if let Ok(id) = name.parse() {
+      return self.by_id(id).await;
+    }
+
+    let name_url = resource_url::<T>(&self.base_url, name);
+    if let Some(url) = self.cache.resolve(&name_url) {
+      let val = self.request_json(&url).await?;
+      self.cache.remember_alias(&name_url, &url);
+      return Ok(val);
+    }
+
+    // We don't know this resource's ID yet, so we need to request it by name
+    // and file it under its ID, if it has one.
+    let response = self.fetch(&name_url, None).await?;
+    let url = match id_of(&response) {
+      Some(id) => resource_url::<T>(&self.base_url, id),
+      None => return self.request_json_with(&name_url, Some(response)).await,
+    };
+    let val = self.request_json_with(&url, Some(response)).await?;
+    self.cache.alias(&name_url, &url);
+    Ok(val)
+  }
+
+  /// Try to get the specific resource of type `T` with the given ID.
+  pub async fn by_id<T: Endpoint>(&self, id: u32) -> Result<Arc<T>, Error> {
+    self
+      .request_json(&resource_url::<T>(&self.base_url, id))
+      .await
+  }
+
+  /// Try to get the specific resource of type `T` at the given URL.
+  ///
+  /// See [`Api::by_url()`].
+  pub async fn by_url<T: Endpoint>(&self, url: &str) -> Result<Arc<T>, Error> {
+    let name = resource_name::<T>(&self.base_url, url)
+      .ok_or_else(|| Error::new(url, ErrorKind::WrongEndpoint(T::NAME)))?;
+    self.by_name::<T>(name).await
+  }
+
+  /// Try to get the specific resource of type `T` with the given variant.
+  pub async fn by_variant<T: Endpoint + Named>(
+    &self,
+    variant: T::Variant,
+  ) -> Result<Arc<T>, Error> {
+    self.by_name::<T>(variant.to_str()).await
+  }
+}
+
+/// A lazy, asynchronous listing over all resources of type `T`.
+///
+/// This type is the asynchronous counterpart to [`Listing`].
+///
+/// This type is generic on the pointer type for [`AsyncApi`]; for example,
+/// both a normal reference and an `Arc` may be passed to `new`.
+#[derive(Clone)]
+pub struct AsyncListing<T, A> {
+  api: A,
+  page: Option<Arc<Page<T>>>,
+  offset: usize,
+  per_page: usize,
+}
+
+impl<T: Endpoint, A: Borrow<AsyncApi>> AsyncListing<T, A> {
+  /// Creates a new resource listing.
+  ///
+  /// This function does nothing on its own; [`AsyncListing::advance()`] must
+  /// be called to drive network requests forward.
+  pub fn new(api: A, per_page: usize) -> Self {
+    Self {
+      api,
+      page: None,
+      offset: 0,
+      per_page,
+    }
+  }
+
+  /// Drives this listing forward by requesting the next page in the listing.
+  pub async fn advance(&mut self) -> Result<Option<ListingResults<T>>, Error> {
+    match self.page.as_ref() {
+      Some(page) if page.next.is_none() => Ok(None),
+      Some(page) => {
+        let offset = self.offset + page.results.len();
+        self.seek(offset).await
+      }
+      None => self.seek(0).await,
+    }
+  }
+
+  /// Drives this listing backward by requesting the previous page in the
+  /// listing.
+  ///
+  /// See [`Listing::retreat()`].
+  pub async fn retreat(&mut self) -> Result<Option<ListingResults<T>>, Error> {
+    if self.page.is_some() {
+      if self.offset == 0 {
+        return Ok(None);
+      }
+      // After an unaligned seek, the previous page may be a short one.
+      let limit = self.per_page.min(self.offset);
+      return self.seek_with_limit(self.offset - limit, limit).await;
+    }
+
+    match self.len().await? {
+      0 => Ok(None),
+      len => self.page_at((len - 1) / self.per_page.max(1)).await,
+    }
+  }
+
+  /// Moves this listing to the page starting at the `offset`th resource,
+  /// returning its results.
+  ///
+  /// See [`Listing::seek()`].
+  pub async fn seek(
+    &mut self,
+    offset: usize,
+  ) -> Result<Option<ListingResults<T>>, Error> {
+    self.seek_with_limit(offset, self.per_page).await
+  }
+
+  /// Like [`AsyncListing::seek()`], but requests at most `limit` resources.
+  async fn seek_with_limit(
+    &mut self,
+    offset: usize,
+    limit: usize,
+  ) -> Result<Option<ListingResults<T>>, Error> {
+    let api = self.api.borrow();
+    let url = listing_url::<T>(&api.base_url, offset, limit);
+    let page = api.request_json::<Page<_>>(&url).await?;
+    if page.results.is_empty() && offset > 0 {
+      return Ok(None);
+    }
+
+    self.page = Some(page);
+    self.offset = offset;
+    Ok(self.current_results())
+  }
+
+  /// Moves this listing to the `n`th page, counting from zero, returning its
+  /// results.
+  ///
+  /// See [`Listing::page_at()`].
+  pub async fn page_at(
+    &mut self,
+    n: usize,
+  ) -> Result<Option<ListingResults<T>>, Error> {
+    self.seek(n.saturating_mul(self.per_page)).await
+  }
+
+  /// Returns the offset of the first resource in the current page, if a page
+  /// has been requested.
+  pub fn offset(&self) -> Option<usize> {
+    self.page.as_ref().map(|_| self.offset)
+  }
+
+  /// Returns the total number of resources in this listing.
+  ///
+  /// See [`Listing::len()`].
+  pub async fn len(&self) -> Result<usize, Error> {
+    if let Some(page) = &self.page {
+      return Ok(page.count as usize);
+    }
+
+    let api = self.api.borrow();
+    let url = listing_url::<T>(&api.base_url, 0, self.per_page);
+    Ok(api.request_json::<Page<T>>(&url).await?.count as usize)
+  }
+
+  /// Returns whether this listing is empty.
+  ///
+  /// See [`Listing::len()`].
+  pub async fn is_empty(&self) -> Result<bool, Error> {
+    Ok(self.len().await? == 0)
+  }
+
+  /// Returns a copy of the results for the current page.
+  pub fn current_results(&self) -> Option<ListingResults<T>> {
+    self.page.as_ref().map(|p| ListingResults {
+      page: Arc::clone(p),
+    })
+  }
+
+  /// Returns an estimate for the total number of resources in this listing, if
+  /// one is available.
+  pub fn estimate_len(&self) -> Option<usize> {
+    self.page.as_ref().map(|p| p.count as usize)
+  }
+}
@@ -0,0 +1,132 @@
+//! Retries and rate limiting for requests.
+
+use std::io;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::api::ErrorKind;
+
+/// A policy for retrying failed requests.
+///
+/// Only transient failures, such as timeouts or dropped connections, are
+/// retried. The delay between attempts grows exponentially, starting at
+/// `initial_backoff` and multiplying by `multiplier` after every attempt, up to
+/// `max_backoff`.
+#[derive(Copy, Clone, Debug)]
+pub struct Retry {
+  /// The maximum number of attempts to make, including the first one.
+  ///
+  /// A value of zero or one disables retries.
+  pub max_attempts: u32,
+  /// The delay before the first retry.
+  pub initial_backoff: Duration,
+  /// The maximum delay between two attempts.
+  pub max_backoff: Duration,
+  /// The factor by which the delay grows after every attempt.
+  pub multiplier: f64,
+}
+
+impl Retry {
+  /// Returns a policy that never retries.
+  pub fn never() -> Self {
+    Self {
+      max_attempts: 1,
+      ..Self::default()
+    }
+  }
+
+  /// Returns how long to wait before retrying a request that failed on its
+  /// `attempts`th attempt with `error`, or `None` if it should not be retried.
+  pub fn backoff(&self, attempts: u32, error: &ErrorKind) -> Option<Duration> {
+    if attempts >= self.max_attempts || !error.is_transient() {
+      return None;
+    }
+
+    let factor = self.multiplier.max(1.0).powi(attempts as i32 - 1);
+    let backoff = self.initial_backoff.as_secs_f64() * factor;
+    let backoff =
+      Duration::from_secs_f64(backoff.min(self.max_backoff.as_secs_f64()));
+
+    // If the server told us how long to wait, wait at least that long.
+    match error {
+      ErrorKind::RateLimited {
+        retry_after: Some(retry_after),
+        ..
+      } => Some(backoff.max(*retry_after)),
+      _ => Some(backoff),
+    }
+  }
+}
+
+impl Default for Retry {
+  fn default() -> Self {
+    Self {
+      max_attempts: 3,
+      initial_backoff: Duration::from_millis(500),
+      max_backoff: Duration::from_secs(30),
+      multiplier: 2.0,
+    }
+  }
+}
+
+impl ErrorKind {
+  /// Returns whether this error is likely to go away if the request is made
+  /// again.
+  pub fn is_transient(&self) -> bool {
+    match self {
+      Self::Io(e) => matches!(
+        e.kind(),
+        io::ErrorKind::Interrupted
+          | io::ErrorKind::TimedOut
+          | io::ErrorKind::ConnectionReset
+          | io::ErrorKind::ConnectionAborted
+          | io::ErrorKind::UnexpectedEof
+      ),
+      Self::Http(e) => e.is_timeout() || e.is_connect() || e.is_request(),
+      Self::RateLimited { .. } | Self::ServerError(_) => true,
+      Self::Json(_)
+      | Self::NotFound(_)
+      | Self::Status(_)
+      | Self::WrongEndpoint(_) => false,
+    }
+  }
+}
+
+/// A rate limiter, which spaces requests out evenly.
+pub(in crate::api) struct Throttle {
+  interval: Option<Duration>,
+  next: Mutex<Instant>,
+}
+
+impl Throttle {
+  /// Creates a new [`Throttle`] that allows at most `per_second` requests each
+  /// second; `None` imposes no limit.
+  ///
+  /// Rates so low that the interval between requests does not fit in a
+  /// [`Duration`] impose no limit either.
+  pub fn new(per_second: Option<f64>) -> Self {
+    Self {
+      interval: per_second
+        .filter(|&n| n > 0.0)
+        .and_then(|n| Duration::try_from_secs_f64(1.0 / n).ok()),
+      next: Mutex::new(Instant::now()),
+    }
+  }
+
+  /// Reserves a slot for a request, returning how long the caller must wait
+  /// before making it.
+  pub fn reserve(&self) -> Duration {
+    let interval = match self.interval {
+      Some(interval) => interval,
+      None => return Duration::default(),
+    };
+
+    let mut next = self.next.lock().unwrap();
+    let now = Instant::now();
+    let slot = (*next).max(now);
+    // Like in `new()`, an interval too long to keep track of imposes no limit.
+    *next = slot.checked_add(interval).unwrap_or(slot);
+    slot - now
+  }
+}
@@ -0,0 +1,540 @@
+//! Transports, which perform the raw requests made by an [`Api`].
+//!
+//! By default, an [`Api`] talks to PokéAPI over HTTP, but any [`Transport`]
+//! can be plugged in through [`Options`]. This makes it possible to, for
+//! example, run against a snapshot of PokéAPI responses without network
+//! access, or against a local copy of PokéAPI's static data with [`Dump`].
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use reqwest::blocking::Client;
+use reqwest::header;
+use reqwest::header::HeaderMap;
+use reqwest::header::HeaderValue;
+use reqwest::StatusCode;
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::api::ErrorKind;
+
+#[cfg(doc)]
+use crate::api::Api;
+#[cfg(doc)]
+use crate::api::Options;
+
+/// A way of fetching the contents of a URL.
+///
+/// Implementations need not perform any caching; that is done by the [`Api`]
+/// itself.
+pub trait Transport: Any + Send + Sync {
+  /// Performs a GET request for `url`, returning the body of the response.
+  ///
+  /// Unsuccessful responses must be reported as errors, such as
+  /// [`ErrorKind::NotFound`], rather than returning their body; only successful
+  /// responses are written to the cache.
+  fn get(&self, url: &str) -> Result<Vec<u8>, ErrorKind>;
+
+  /// Performs a conditional GET request for `url`.
+  ///
+  /// `cached` describes a previously fetched copy of the response, if there is
+  /// one; if it is still current, the transport may return
+  /// [`Conditional::NotModified`] instead of downloading it again.
+  ///
+  /// The default implementation ignores `cached` and calls
+  /// [`Transport::get()`].
+  fn get_conditional(
+    &self,
+    url: &str,
+    cached: Option<&Freshness>,
+  ) -> Result<Conditional, ErrorKind> {
+    let _ = cached;
+    Ok(Conditional::Modified(self.get(url)?, Freshness::default()))
+  }
+}
+
+/// The result of a conditional request; see [`Transport::get_conditional()`].
+#[derive(Clone, Debug)]
+pub enum Conditional<T = Vec<u8>> {
+  /// The resource has changed (or was not previously cached), and this is its
+  /// new value.
+  Modified(T, Freshness),
+  /// The cached copy of the resource is still current.
+  NotModified(Freshness),
+}
+
+impl<T> Conditional<T> {
+  /// Applies a fallible function to the value of a [`Conditional::Modified`].
+  pub(in crate::api) fn try_map<U, E>(
+    self,
+    f: impl FnOnce(T) -> Result<U, E>,
+  ) -> Result<Conditional<U>, E> {
+    match self {
+      Self::Modified(val, freshness) => {
+        Ok(Conditional::Modified(f(val)?, freshness))
+      }
+      Self::NotModified(freshness) => Ok(Conditional::NotModified(freshness)),
+    }
+  }
+}
+
+/// Caching information for a response.
+///
+/// This records how long a response may be cached for, and the validators
+/// needed to cheaply check whether it has changed once it goes stale.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Freshness {
+  /// How long the response may be cached for, according to the server.
+  pub max_age: Option<Duration>,
+  /// The response's `ETag`, if it had one.
+  pub etag: Option<String>,
+  /// The response's `Last-Modified` date, if it had one.
+  pub last_modified: Option<String>,
+}
+
+impl Freshness {
+  /// Extracts caching information from HTTP response headers.
+  pub(in crate::api) fn from_headers(headers: &HeaderMap) -> Self {
+    let header = |name| {
+      let value = headers.get(name)?.to_str().ok()?;
+      Some(value.to_string())
+    };
+
+    let max_age = header(header::CACHE_CONTROL).and_then(|cc| {
+      cc.split(',')
+        .map(str::trim)
+        .find_map(|directive| match directive {
+          "no-cache" | "no-store" => Some(0),
+          _ => directive.strip_prefix("max-age=")?.parse().ok(),
+        })
+    });
+
+    Self {
+      max_age: max_age.map(Duration::from_secs),
+      etag: header(header::ETAG),
+      last_modified: header(header::LAST_MODIFIED),
+    }
+  }
+
+  /// Returns the headers needed to make a request conditional on the response
+  /// described by `self` having changed.
+  pub(in crate::api) fn to_headers(&self) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    let mut insert = |name, value: &Option<String>| {
+      if let Some(value) = value.as_deref() {
+        if let Ok(value) = HeaderValue::from_str(value) {
+          headers.insert(name, value);
+        }
+      }
+    };
+
+    insert(header::IF_NONE_MATCH, &self.etag);
+    insert(header::IF_MODIFIED_SINCE, &self.last_modified);
+    headers
+  }
+
+  /// Updates `self` with the information from a [`Conditional::NotModified`]
+  /// response.
+  pub(in crate::api) fn refresh(&mut self, new: Freshness) {
+    self.max_age = new.max_age.or(self.max_age);
+    self.etag = new.etag.or_else(|| self.etag.take());
+    self.last_modified =
+      new.last_modified.or_else(|| self.last_modified.take());
+  }
+}
+
+/// Converts an unsuccessful HTTP status into an error.
+///
+/// [`Http`] uses this to classify responses; other transports that speak HTTP
+/// can use it to report errors the same way.
+pub fn check_status(
+  status: StatusCode,
+  headers: &HeaderMap,
+) -> Result<(), ErrorKind> {
+  let code = status.as_u16();
+  match code {
+    200..=299 => Ok(()),
+    404 | 410 => Err(ErrorKind::NotFound(code)),
+    429 => Err(ErrorKind::RateLimited {
+      status: code,
+      retry_after: headers
+        .get(header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok()?.trim().parse().ok())
+        .map(Duration::from_secs),
+    }),
+    500..=599 => Err(ErrorKind::ServerError(code)),
+    _ => Err(ErrorKind::Status(code)),
+  }
+}
+
+/// Converts an error from reading a file into an [`ErrorKind`], treating
+/// missing files as missing resources.
+fn file_error(e: io::Error) -> ErrorKind {
+  match e.kind() {
+    io::ErrorKind::NotFound => ErrorKind::NotFound(404),
+    _ => e.into(),
+  }
+}
+
+/// A [`Transport`] that performs HTTP requests using `reqwest`.
+///
+/// This is the default transport.
+///
+/// An `AsyncApi` cannot use the blocking client, so with the `async` feature
+/// enabled this transport also carries an asynchronous client, which an
+/// `AsyncApi` built from it uses instead. The two clients are configured
+/// separately; see `Http::with_async_client()`.
+#[derive(Clone, Default)]
+pub struct Http {
+  client: Client,
+  #[cfg(feature = "async")]
+  async_client: reqwest::Client,
+}
+
+impl Http {
+  /// Creates a new [`Http`] transport with default clients.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Creates a new [`Http`] transport that makes blocking requests with
+  /// `client`.
+  pub fn with_client(client: Client) -> Self {
+    Self {
+      client,
+      #[cfg(feature = "async")]
+      async_client: reqwest::Client::new(),
+    }
+  }
+
+  /// Makes an `AsyncApi` built from this transport perform requests with
+  /// `client`.
+  ///
+  /// Settings such as proxies, timeouts and the user agent must be set on
+  /// `client` as well as on the blocking client for them to apply to both.
+  #[cfg(feature = "async")]
+  pub fn with_async_client(self, client: reqwest::Client) -> Self {
+    Self {
+      async_client: client,
+      ..self
+    }
+  }
+
+  /// Returns the client an `AsyncApi` performs requests with.
+  #[cfg(feature = "async")]
+  pub(in crate::api) fn async_client(&self) -> &reqwest::Client {
+    &self.async_client
+  }
+}
+
+impl Transport for Http {
+  fn get(&self, url: &str) -> Result<Vec<u8>, ErrorKind> {
+    match self.get_conditional(url, None)? {
+      Conditional::Modified(buf, _) => Ok(buf),
+      Conditional::NotModified(_) => Err(ErrorKind::Status(304)),
+    }
+  }
+
+  fn get_conditional(
+    &self,
+    url: &str,
+    cached: Option<&Freshness>,
+  ) -> Result<Conditional, ErrorKind> {
+    let mut request = self.client.get(url);
+    if let Some(cached) = cached {
+      request = request.headers(cached.to_headers());
+    }
+
+    let mut response = request.send()?;
+    let freshness = Freshness::from_headers(response.headers());
+    if cached.is_some() && response.status() == StatusCode::NOT_MODIFIED {
+      return Ok(Conditional::NotModified(freshness));
+    }
+    check_status(response.status(), response.headers())?;
+
+    let mut buf = Vec::new();
+    response.read_to_end(&mut buf)?;
+    Ok(Conditional::Modified(buf, freshness))
+  }
+}
+
+/// A [`Transport`] that serves requests out of a directory of files.
+///
+/// URLs are mapped to files by [`Directory::path_for()`]. This is intended for
+/// running against a snapshot of PokéAPI responses, such as in tests.
+#[derive(Clone, Debug)]
+pub struct Directory {
+  root: PathBuf,
+}
+
+impl Directory {
+  /// Creates a new [`Directory`] transport rooted at `root`.
+  pub fn new(root: impl Into<PathBuf>) -> Self {
+    Self { root: root.into() }
+  }
+
+  /// Returns the root directory for this transport.
+  pub fn root(&self) -> &Path {
+    &self.root
+  }
+
+  /// Returns the path of the file that `url` maps to.
+  ///
+  /// The scheme is dropped, and the host and each path component become
+  /// directories; if the URL has a query string, it is appended to the last
+  /// component after an `@`, with each character other than an ASCII letter,
+  /// digit, `-` or `.` replaced with `_`. Empty components, such as the one
+  /// after a trailing slash, are skipped. Finally, if the last component has no
+  /// extension, `.json` is added.
+  ///
+  /// For example, `https://pokeapi.co/api/v2/pokemon/?limit=64` maps to
+  /// `pokeapi.co/api/v2/pokemon@limit_64.json`.
+  pub fn path_for(&self, url: &str) -> PathBuf {
+    let url = url.split("://").nth(1).unwrap_or(url);
+    let (url, query) = match url.find('?') {
+      Some(idx) => (&url[..idx], Some(&url[idx + 1..])),
+      None => (url, None),
+    };
+
+    let mut path = self.root.clone();
+    let mut components = url.split('/').filter(|c| !c.is_empty()).peekable();
+    while let Some(component) = components.next() {
+      if components.peek().is_some() {
+        path.push(component);
+        continue;
+      }
+
+      let mut file = component.to_string();
+      if let Some(query) = query {
+        file.push('@');
+        file.extend(query.chars().map(|c| match c {
+          'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '.' => c,
+          _ => '_',
+        }));
+      }
+      if Path::new(component).extension().is_none() {
+        file.push_str(".json");
+      }
+      path.push(file);
+    }
+
+    path
+  }
+}
+
+impl Transport for Directory {
+  fn get(&self, url: &str) -> Result<Vec<u8>, ErrorKind> {
+    fs::read(self.path_for(url)).map_err(file_error)
+  }
+}
+
+/// A [`Transport`] that serves requests out of a static dump of PokéAPI.
+///
+/// PokéAPI publishes its data as a tree of `index.json` files, in the layout
+/// used by the `PokeAPI/api-data` repository: each resource lives at
+/// `api/v2/<endpoint>/<id>/index.json`, and each endpoint's listing lives at
+/// `api/v2/<endpoint>/index.json`. This transport serves requests from such a
+/// tree without touching the network.
+///
+/// Requests by name are resolved to ids through the endpoint's listing, and
+/// listing pages are synthesized from it according to the `offset` and `limit`
+/// query parameters. Since URLs within the dump are relative, they are
+/// rewritten to be absolute, relative to [`Dump::origin()`].
+pub struct Dump {
+  root: PathBuf,
+  origin: String,
+  indices: Mutex<HashMap<String, Arc<Vec<IndexEntry>>>>,
+}
+
+#[derive(Deserialize)]
+struct Index {
+  results: Vec<IndexEntry>,
+}
+
+#[derive(Deserialize)]
+struct IndexEntry {
+  name: Option<String>,
+  url: String,
+}
+
+impl Dump {
+  /// Creates a new [`Dump`] transport rooted at `root`.
+  ///
+  /// `root` should be the directory containing `api/v2`; for a checkout of
+  /// `api-data`, this is its `data` directory. URLs will be rewritten to point
+  /// to `https://pokeapi.co`.
+  pub fn new(root: impl Into<PathBuf>) -> Self {
+    Self::with_origin(root, "https://pokeapi.co")
+  }
+
+  /// Creates a new [`Dump`] transport rooted at `root`, which rewrites URLs to
+  /// point to `origin`.
+  ///
+  /// `origin` should be the scheme and host of the [`Api`]'s base URL.
+  pub fn with_origin(
+    root: impl Into<PathBuf>,
+    origin: impl Into<String>,
+  ) -> Self {
+    Self {
+      root: root.into(),
+      origin: origin.into().trim_end_matches('/').to_string(),
+      indices: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// Returns the root directory for this transport.
+  pub fn root(&self) -> &Path {
+    &self.root
+  }
+
+  /// Returns the origin that URLs are rewritten relative to.
+  pub fn origin(&self) -> &str {
+    &self.origin
+  }
+
+  /// Returns the listing for `endpoint`, loading it if necessary.
+  fn index(&self, endpoint: &str) -> Result<Arc<Vec<IndexEntry>>, ErrorKind> {
+    if let Some(index) = self.indices.lock().unwrap().get(endpoint) {
+      return Ok(Arc::clone(index));
+    }
+
+    let mut path = self.root.clone();
+    path.extend(&["api", "v2", endpoint, "index.json"]);
+    let index: Index =
+      serde_json::from_slice(&fs::read(path).map_err(file_error)?)?;
+    let index = Arc::new(index.results);
+
+    self
+      .indices
+      .lock()
+      .unwrap()
+      .insert(endpoint.to_string(), Arc::clone(&index));
+    Ok(index)
+  }
+
+  /// Resolves `name` to a numeric id for a resource of type `endpoint`.
+  fn resolve(&self, endpoint: &str, name: &str) -> Result<String, ErrorKind> {
+    if name.parse::<u32>().is_ok() {
+      return Ok(name.to_string());
+    }
+
+    let id = self
+      .index(endpoint)?
+      .iter()
+      .find(|e| e.name.as_deref() == Some(name))
+      .and_then(|e| e.url.trim_end_matches('/').rsplit('/').next())
+      .map(str::to_string);
+    id.ok_or(ErrorKind::NotFound(404))
+  }
+
+  /// Synthesizes a page of the listing for `endpoint`.
+  fn page(&self, endpoint: &str, query: &str) -> Result<Value, ErrorKind> {
+    let index = self.index(endpoint)?;
+    let (mut offset, mut limit) = (0, 20);
+    for (key, value) in query.split('&').filter_map(|kv| {
+      let mut kv = kv.splitn(2, '=');
+      Some((kv.next()?, kv.next()?.parse::<usize>().ok()?))
+    }) {
+      match key {
+        "offset" => offset = value,
+        "limit" => limit = value,
+        _ => {}
+      }
+    }
+
+    let start = offset.min(index.len());
+    let end = offset.saturating_add(limit).min(index.len());
+    let url = |offset| {
+      Value::String(format!(
+        "{}/api/v2/{}/?offset={}&limit={}",
+        self.origin, endpoint, offset, limit
+      ))
+    };
+
+    let results = index[start..end]
+      .iter()
+      .map(|e| {
+        let mut entry = serde_json::Map::new();
+        if let Some(name) = &e.name {
+          entry.insert("name".into(), Value::String(name.clone()));
+        }
+        entry.insert("url".into(), Value::String(e.url.clone()));
+        Value::Object(entry)
+      })
+      .collect();
+
+    let mut page = serde_json::Map::new();
+    page.insert("count".into(), index.len().into());
+    page.insert(
+      "next".into(),
+      if end < index.len() {
+        url(end)
+      } else {
+        Value::Null
+      },
+    );
+    page.insert(
+      "previous".into(),
+      if start > 0 {
+        url(start.saturating_sub(limit))
+      } else {
+        Value::Null
+      },
+    );
+    page.insert("results".into(), Value::Array(results));
+    Ok(Value::Object(page))
+  }
+
+  /// Rewrites every relative URL in `value` to be absolute.
+  fn rewrite(&self, value: &mut Value) {
+    match value {
+      Value::String(s) if s.starts_with("/api/") => {
+        s.insert_str(0, &self.origin);
+      }
+      Value::Array(values) => values.iter_mut().for_each(|v| self.rewrite(v)),
+      Value::Object(map) => map.values_mut().for_each(|v| self.rewrite(v)),
+      _ => {}
+    }
+  }
+}
+
+impl Transport for Dump {
+  fn get(&self, url: &str) -> Result<Vec<u8>, ErrorKind> {
+    let url = url.strip_prefix(&self.origin).unwrap_or(url);
+    let (path, query) = match url.find('?') {
+      Some(idx) => (&url[..idx], &url[idx + 1..]),
+      None => (url, ""),
+    };
+
+    let mut components = path.split('/').filter(|c| !c.is_empty());
+    if components.next() != Some("api") || components.next() != Some("v2") {
+      return Err(ErrorKind::NotFound(404));
+    }
+    let components = components.collect::<Vec<_>>();
+
+    let mut value = match components[..] {
+      [] => return Err(ErrorKind::NotFound(404)),
+      [endpoint] => self.page(endpoint, query)?,
+      [endpoint, name, ref rest @ ..] => {
+        let mut path = self.root.clone();
+        path.extend(&["api", "v2", endpoint, &self.resolve(endpoint, name)?]);
+        path.extend(rest);
+        path.push("index.json");
+        serde_json::from_slice(&fs::read(path).map_err(file_error)?)?
+      }
+    };
+
+    self.rewrite(&mut value);
+    Ok(serde_json::to_vec(&value)?)
+  }
+}
@@ -0,0 +1,489 @@
+//! Capture probabilities, which describe how likely a thrown ball is to catch
+//! a wild Pokemon.
+//!
+//! See <https://bulbapedia.bulbagarden.net/wiki/Catch_rate> for the formulas
+//! used.
+
+use crate::model::resource::Name;
+use crate::model::GenerationName;
+use crate::model::Item;
+use crate::model::ItemName;
+use crate::model::Species;
+use crate::model::TypeName;
+
+/// A kind of ball that can be thrown to catch a wild Pokemon.
+///
+/// Balls whose effects depend on things this module does not model, such as
+/// the Heavy Ball or the Love Ball, are treated as Poke Balls.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+pub enum Ball {
+  Poke,
+  Great,
+  Ultra,
+  Master,
+  Safari,
+  Sport,
+  Park,
+  Premier,
+  Luxury,
+  Heal,
+  Cherish,
+  Friend,
+  Heavy,
+  Lure,
+  Moon,
+  Love,
+  Fast,
+  Level,
+  Net,
+  Dive,
+  Nest,
+  Repeat,
+  Timer,
+  Quick,
+  Dusk,
+  Dream,
+  Beast,
+}
+
+/// Every [`Ball`], with its [`Item`] name.
+const BALLS: &[(Ball, ItemName)] = &[
+  (Ball::Poke, ItemName::PokeBall),
+  (Ball::Great, ItemName::GreatBall),
+  (Ball::Ultra, ItemName::UltraBall),
+  (Ball::Master, ItemName::MasterBall),
+  (Ball::Safari, ItemName::SafariBall),
+  (Ball::Sport, ItemName::SportBall),
+  (Ball::Park, ItemName::ParkBall),
+  (Ball::Premier, ItemName::PremierBall),
+  (Ball::Luxury, ItemName::LuxuryBall),
+  (Ball::Heal, ItemName::HealBall),
+  (Ball::Cherish, ItemName::CherishBall),
+  (Ball::Friend, ItemName::FriendBall),
+  (Ball::Heavy, ItemName::HeavyBall),
+  (Ball::Lure, ItemName::LureBall),
+  (Ball::Moon, ItemName::MoonBall),
+  (Ball::Love, ItemName::LoveBall),
+  (Ball::Fast, ItemName::FastBall),
+  (Ball::Level, ItemName::LevelBall),
+  (Ball::Net, ItemName::NetBall),
+  (Ball::Dive, ItemName::DiveBall),
+  (Ball::Nest, ItemName::NestBall),
+  (Ball::Repeat, ItemName::RepeatBall),
+  (Ball::Timer, ItemName::TimerBall),
+  (Ball::Quick, ItemName::QuickBall),
+  (Ball::Dusk, ItemName::DuskBall),
+  (Ball::Dream, ItemName::DreamBall),
+  (Ball::Beast, ItemName::BeastBall),
+];
+
+impl Ball {
+  /// Returns the ball with the given [`Item`] API name, if any.
+  pub fn from_name(name: &str) -> Option<Self> {
+    Self::from_item_name(name.parse().ok()?)
+  }
+
+  /// Returns the ball with the given well-known [`Item`] name, if any.
+  pub fn from_item_name(name: ItemName) -> Option<Self> {
+    BALLS.iter().find(|&&(_, n)| n == name).map(|&(b, _)| b)
+  }
+
+  /// Returns the ball `item` is, if any.
+  pub fn of(item: &Item) -> Option<Self> {
+    Self::from_item_name(item.name.variant()?)
+  }
+
+  /// Returns this ball's well-known [`Item`] name.
+  pub fn item_name(self) -> ItemName {
+    BALLS.iter().find(|&&(b, _)| b == self).unwrap().1
+  }
+
+  /// Returns this ball's [`Item`] API name.
+  pub fn name(self) -> &'static str {
+    self.item_name().to_str()
+  }
+
+  /// Returns whether this ball always succeeds.
+  fn always_catches(self) -> bool {
+    matches!(self, Self::Master | Self::Park)
+  }
+}
+
+/// A non-volatile status condition, which makes a Pokemon easier to catch.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+pub enum Status {
+  #[default]
+  None,
+  Sleep,
+  Freeze,
+  Paralysis,
+  Burn,
+  Poison,
+}
+
+/// The odds of catching a Pokemon, as returned by [`Capture::chance()`].
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Chance {
+  /// The probability that a single throw catches the Pokemon, including
+  /// through a critical capture.
+  pub per_throw: f64,
+  /// The probability that a single throw results in a critical capture, which
+  /// only needs to pass one shake check instead of all of them.
+  ///
+  /// Critical captures exist from Generation V onward.
+  pub critical: f64,
+}
+
+/// A calculator for the odds of catching a wild Pokemon.
+///
+/// ```
+/// # use pkmn::calc::capture::*;
+/// # use pkmn::model::GenerationName;
+/// // A Pokemon with a capture rate of 45 at a third of its HP, asleep.
+/// let chance = Capture::new(45, 150)
+///   .hp(50)
+///   .status(Status::Sleep)
+///   .ball(Ball::Ultra)
+///   .chance(GenerationName::IV);
+/// assert!(chance.per_throw > 0.5);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Capture {
+  capture_rate: u8,
+  max_hp: u32,
+  hp: u32,
+  status: Status,
+  ball: Ball,
+
+  level: u8,
+  own_level: u8,
+  water_or_bug: bool,
+  turn: u32,
+  dark: bool,
+  underwater: bool,
+  caught_before: bool,
+  caught_species: u32,
+  ultra_beast: bool,
+}
+
+impl Capture {
+  /// Creates a new [`Capture`] for a Pokemon with the given capture rate and
+  /// maximum HP, at full HP and with no status, being thrown a Poke Ball.
+  pub fn new(capture_rate: u8, max_hp: u32) -> Self {
+    Self {
+      capture_rate,
+      max_hp,
+      hp: max_hp,
+      status: Status::None,
+      ball: Ball::Poke,
+
+      level: 1,
+      own_level: 1,
+      water_or_bug: false,
+      turn: 1,
+      dark: false,
+      underwater: false,
+      caught_before: false,
+      caught_species: 0,
+      ultra_beast: false,
+    }
+  }
+
+  /// Creates a new [`Capture`] for a Pokemon of `species`, using its
+  /// [`Species::capture_rate`].
+  pub fn for_species(species: &Species, max_hp: u32) -> Self {
+    Self::new(species.capture_rate, max_hp)
+  }
+
+  /// Sets the Pokemon's current HP.
+  #[inline]
+  pub fn hp(mut self, hp: u32) -> Self {
+    self.hp = hp;
+    self
+  }
+
+  /// Sets the Pokemon's status condition.
+  #[inline]
+  pub fn status(mut self, status: Status) -> Self {
+    self.status = status;
+    self
+  }
+
+  /// Sets the ball being thrown.
+  #[inline]
+  pub fn ball(mut self, ball: Ball) -> Self {
+    self.ball = ball;
+    self
+  }
+
+  /// Sets the Pokemon's level, for the Nest and Level Balls.
+  #[inline]
+  pub fn level(mut self, level: u8) -> Self {
+    self.level = level;
+    self
+  }
+
+  /// Sets the level of the player's active Pokemon, for the Level Ball.
+  #[inline]
+  pub fn own_level(mut self, level: u8) -> Self {
+    self.own_level = level;
+    self
+  }
+
+  /// Sets the Pokemon's types, for the Net Ball.
+  #[inline]
+  pub fn types(mut self, types: &[TypeName]) -> Self {
+    self.water_or_bug = types
+      .iter()
+      .any(|&t| t == TypeName::Water || t == TypeName::Bug);
+    self
+  }
+
+  /// Sets the turn of the battle the ball is thrown on, starting at 1, for
+  /// the Timer and Quick Balls.
+  #[inline]
+  pub fn turn(mut self, turn: u32) -> Self {
+    self.turn = turn;
+    self
+  }
+
+  /// Sets whether it is night time or the battle is in a cave, for the Dusk
+  /// Ball.
+  #[inline]
+  pub fn dark(mut self, dark: bool) -> Self {
+    self.dark = dark;
+    self
+  }
+
+  /// Sets whether the Pokemon was found underwater, while surfing or while
+  /// fishing, for the Dive Ball.
+  #[inline]
+  pub fn underwater(mut self, underwater: bool) -> Self {
+    self.underwater = underwater;
+    self
+  }
+
+  /// Sets whether the player has caught this species before, for the Repeat
+  /// Ball.
+  #[inline]
+  pub fn caught_before(mut self, caught_before: bool) -> Self {
+    self.caught_before = caught_before;
+    self
+  }
+
+  /// Sets how many species the player has caught, which makes critical
+  /// captures more likely.
+  #[inline]
+  pub fn caught_species(mut self, caught_species: u32) -> Self {
+    self.caught_species = caught_species;
+    self
+  }
+
+  /// Sets whether the Pokemon is an Ultra Beast, for the Beast Ball.
+  #[inline]
+  pub fn ultra_beast(mut self, ultra_beast: bool) -> Self {
+    self.ultra_beast = ultra_beast;
+    self
+  }
+
+  /// Computes the odds of catching the Pokemon with a single throw in
+  /// `generation`.
+  ///
+  /// Whether the ball exists in `generation` is not checked; balls from later
+  /// generations are treated as Poke Balls in Generations I and II.
+  pub fn chance(&self, generation: GenerationName) -> Chance {
+    if self.ball.always_catches() {
+      return Chance {
+        per_throw: 1.0,
+        critical: 0.0,
+      };
+    }
+
+    match generation {
+      GenerationName::I => self.gen1(),
+      GenerationName::II => self.gen2(),
+      _ => self.modern(generation),
+    }
+  }
+
+  /// Computes the odds of catching the Pokemon in Generation I, which does
+  /// not use a catch value.
+  fn gen1(&self) -> Chance {
+    let range = match self.ball {
+      Ball::Great => 201,
+      Ball::Ultra | Ball::Safari => 151,
+      _ => 256,
+    };
+    let status = match self.status {
+      Status::None => 0,
+      Status::Sleep | Status::Freeze => 25,
+      _ => 12,
+    };
+    let divisor = if self.ball == Ball::Great { 8 } else { 12 };
+    let hp = (self.hp / 4).max(1);
+    let shake = (self.max_hp * 255 / divisor / hp).min(255);
+
+    // Random numbers below the status bonus always succeed; the rest succeed
+    // if they are within the capture rate and pass a shake check.
+    let always = status.min(range);
+    let in_rate = (status + self.capture_rate as u32 + 1)
+      .min(range)
+      .saturating_sub(status);
+    let per_throw = (always as f64
+      + in_rate as f64 * (shake + 1) as f64 / 256.0)
+      / range as f64;
+    Chance {
+      per_throw,
+      critical: 0.0,
+    }
+  }
+
+  /// Computes the odds of catching the Pokemon in Generation II.
+  fn gen2(&self) -> Chance {
+    let ball = match self.ball {
+      Ball::Great => 1.5,
+      Ball::Ultra => 2.0,
+      Ball::Level => self.level_ball(),
+      _ => 1.0,
+    };
+    let rate = (self.capture_rate as f64 * ball).min(255.0) as u32;
+    let max = 3 * self.max_hp.max(1);
+    let hp = max.saturating_sub(2 * self.hp);
+    let mut a = (hp * rate / max).max(1);
+
+    // Only sleep and freezing have an effect, because of a bug.
+    if matches!(self.status, Status::Sleep | Status::Freeze) {
+      a += 10;
+    }
+    Chance {
+      per_throw: (a.min(255) + 1) as f64 / 256.0,
+      critical: 0.0,
+    }
+  }
+
+  /// Computes the odds of catching the Pokemon from Generation III onward.
+  fn modern(&self, generation: GenerationName) -> Chance {
+    let status = match self.status {
+      Status::None => 1.0,
+      Status::Sleep | Status::Freeze if generation >= GenerationName::V => 2.5,
+      Status::Sleep | Status::Freeze => 2.0,
+      _ => 1.5,
+    };
+    let max = 3.0 * self.max_hp.max(1) as f64;
+    let hp = (max - 2.0 * self.hp as f64).max(1.0);
+    let a = (hp * self.capture_rate as f64 * self.ball_bonus(generation) / max
+      * status)
+      .floor();
+    if a >= 255.0 {
+      return Chance {
+        per_throw: 1.0,
+        critical: 0.0,
+      };
+    }
+
+    let (shake, checks) = if generation >= GenerationName::V {
+      let b = (65536.0 / (255.0 / a).powf(3.0 / 16.0)).floor();
+      let checks = if generation == GenerationName::V {
+        3
+      } else {
+        4
+      };
+      (b / 65536.0, checks)
+    } else {
+      let b = (1048560.0 / (16711680.0 / a).sqrt().sqrt()).floor();
+      (b / 65536.0, 4)
+    };
+    let shake = shake.min(1.0);
+
+    let critical = if generation >= GenerationName::V {
+      let bonus = match self.caught_species {
+        0..=30 => 0.0,
+        31..=150 => 0.5,
+        151..=300 => 1.0,
+        301..=450 => 1.5,
+        451..=600 => 2.0,
+        _ => 2.5,
+      };
+      (a * bonus / 6.0).floor() / 256.0
+    } else {
+      0.0
+    };
+
+    Chance {
+      per_throw: critical * shake + (1.0 - critical) * shake.powi(checks),
+      critical,
+    }
+  }
+
+  /// Returns the catch rate multiplier for the ball in `generation`, from
+  /// Generation III onward.
+  fn ball_bonus(&self, generation: GenerationName) -> f64 {
+    use GenerationName::*;
+    let t = self.turn.saturating_sub(1) as f64;
+    match self.ball {
+      Ball::Great | Ball::Safari | Ball::Sport => 1.5,
+      Ball::Ultra => 2.0,
+      Ball::Level => self.level_ball(),
+      Ball::Net if self.water_or_bug => {
+        if generation >= VII {
+          3.5
+        } else {
+          3.0
+        }
+      }
+      Ball::Repeat if self.caught_before => {
+        if generation >= VII {
+          3.5
+        } else {
+          3.0
+        }
+      }
+      Ball::Dive if self.underwater => 3.5,
+      Ball::Nest => {
+        let base = if generation >= V { 41.0 } else { 40.0 };
+        ((base - self.level as f64) / 10.0).max(1.0)
+      }
+      Ball::Timer if generation >= V => (1.0 + t * 1229.0 / 4096.0).min(4.0),
+      Ball::Timer => ((t + 10.0) / 10.0).min(4.0),
+      Ball::Quick if self.turn <= 1 => {
+        if generation >= V {
+          5.0
+        } else {
+          4.0
+        }
+      }
+      Ball::Dusk if self.dark => {
+        if generation >= VII {
+          3.0
+        } else {
+          3.5
+        }
+      }
+      Ball::Dream if generation >= VIII && self.status == Status::Sleep => 4.0,
+      Ball::Beast if generation >= VII => {
+        if self.ultra_beast {
+          5.0
+        } else {
+          0.1
+        }
+      }
+      _ => 1.0,
+    }
+  }
+
+  /// Returns the Level Ball's multiplier.
+  fn level_ball(&self) -> f64 {
+    let (own, level) = (self.own_level as u32, self.level as u32);
+    if own >= 4 * level {
+      8.0
+    } else if own >= 2 * level {
+      4.0
+    } else if own > level {
+      2.0
+    } else {
+      1.0
+    }
+  }
+}
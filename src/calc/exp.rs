@@ -0,0 +1,264 @@
+//! Experience, which Pokemon gain in battle in order to grow levels.
+//!
+//! See <https://bulbapedia.bulbagarden.net/wiki/Experience> for the formulas
+//! used.
+
+use crate::calc::stats::MAX_LEVEL;
+use crate::model::species::GrowthRate;
+use crate::model::species::GrowthRateName;
+use crate::model::GenerationName;
+use crate::model::Pokemon;
+
+impl GrowthRateName {
+  /// Returns the total experience a Pokemon needs to reach `level`.
+  ///
+  /// Levels past [`MAX_LEVEL`] are treated as [`MAX_LEVEL`].
+  pub fn experience_at(self, level: u8) -> u32 {
+    if level <= 1 {
+      return 0;
+    }
+    let n = level.min(MAX_LEVEL) as i64;
+    let cube = n * n * n;
+    let exp = match self {
+      Self::Fast => 4 * cube / 5,
+      Self::Medium => cube,
+      Self::MediumSlow => 6 * cube / 5 - 15 * n * n + 100 * n - 140,
+      Self::Slow => 5 * cube / 4,
+      Self::Erratic => match n {
+        0..=49 => cube * (100 - n) / 50,
+        50..=67 => cube * (150 - n) / 100,
+        68..=97 => cube * ((1911 - 10 * n) / 3) / 500,
+        _ => cube * (160 - n) / 100,
+      },
+      Self::Fluctuating => match n {
+        0..=14 => cube * ((n + 1) / 3 + 24) / 50,
+        15..=35 => cube * (n + 14) / 50,
+        _ => cube * (n / 2 + 32) / 50,
+      },
+    };
+    exp.max(0) as u32
+  }
+
+  /// Returns the level of a Pokemon with `exp` total experience.
+  pub fn level_at(self, exp: u32) -> u8 {
+    level_at(|level| Some(self.experience_at(level)), exp).unwrap_or(1)
+  }
+
+  /// Returns how much more experience a Pokemon with `exp` total experience
+  /// needs to reach its next level, or `None` if it is at [`MAX_LEVEL`].
+  pub fn experience_to_next(self, exp: u32) -> Option<u32> {
+    experience_to_next(|level| Some(self.experience_at(level)), exp)
+  }
+}
+
+impl GrowthRate {
+  /// Returns the total experience a Pokemon needs to reach `level`.
+  ///
+  /// This uses [`GrowthRate::levels`], falling back to the formula for this
+  /// growth rate's [`GrowthRateName`] for levels missing from it; `None` is
+  /// returned if neither is available.
+  pub fn experience_at(&self, level: u8) -> Option<u32> {
+    let level = level.clamp(1, MAX_LEVEL);
+    self
+      .levels
+      .iter()
+      .find(|l| l.level == level as u32)
+      .map(|l| l.experience)
+      .or_else(|| Some(self.name.variant()?.experience_at(level)))
+  }
+
+  /// Returns the level of a Pokemon with `exp` total experience.
+  ///
+  /// Returns `None` if [`GrowthRate::experience_at()`] does.
+  pub fn level_at(&self, exp: u32) -> Option<u8> {
+    level_at(|level| self.experience_at(level), exp)
+  }
+
+  /// Returns how much more experience a Pokemon with `exp` total experience
+  /// needs to reach its next level.
+  ///
+  /// Returns `None` if it is at [`MAX_LEVEL`], or if
+  /// [`GrowthRate::experience_at()`] does.
+  pub fn experience_to_next(&self, exp: u32) -> Option<u32> {
+    experience_to_next(|level| self.experience_at(level), exp)
+  }
+}
+
+/// Returns the highest level whose experience, as given by `experience_at`,
+/// is at most `exp`.
+fn level_at(experience_at: impl Fn(u8) -> Option<u32>, exp: u32) -> Option<u8> {
+  let mut reached = 1;
+  for level in 2..=MAX_LEVEL {
+    if experience_at(level)? > exp {
+      break;
+    }
+    reached = level;
+  }
+  Some(reached)
+}
+
+/// Returns how far `exp` is from the next level, with levels given by
+/// `experience_at`.
+fn experience_to_next(
+  experience_at: impl Fn(u8) -> Option<u32>,
+  exp: u32,
+) -> Option<u32> {
+  let level = level_at(&experience_at, exp)?;
+  if level >= MAX_LEVEL {
+    return None;
+  }
+  Some(experience_at(level + 1)? - exp)
+}
+
+/// A formula for computing experience gained in battle, which depends on the
+/// game.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Formula {
+  /// The formula used in Generations I through IV, as well as Generation VI.
+  ///
+  /// Experience is proportional to the defeated Pokemon's level.
+  Flat,
+  /// The formula used in Generation V.
+  ///
+  /// Experience is scaled by the difference between the defeated Pokemon's
+  /// level and the victor's: defeating stronger Pokemon is worth more.
+  Scaled,
+  /// The formula used from Generation VII onward.
+  ///
+  /// Experience is scaled as with [`Formula::Scaled`], but there is no bonus
+  /// for defeating a Pokemon owned by a trainer.
+  Modern,
+}
+
+impl Formula {
+  /// Returns the formula used by the main series games of `generation`.
+  pub fn for_generation(generation: GenerationName) -> Self {
+    match generation {
+      GenerationName::V => Self::Scaled,
+      GenerationName::VI => Self::Flat,
+      g if g < GenerationName::V => Self::Flat,
+      _ => Self::Modern,
+    }
+  }
+}
+
+/// A calculator for the experience a Pokemon gains by defeating another.
+///
+/// ```
+/// # use pkmn::calc::exp::*;
+/// // A level 50 wild Pokemon with a base experience yield of 64.
+/// let gain = ExpGain::new(64, 50);
+/// assert_eq!(gain.compute(Formula::Flat), 457);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ExpGain {
+  base_experience: u32,
+  level: u8,
+  victor_level: u8,
+  trainer: bool,
+  traded: bool,
+  international: bool,
+  lucky_egg: bool,
+  participants: u32,
+}
+
+impl ExpGain {
+  /// Creates a new [`ExpGain`] for defeating a wild Pokemon at `level`, whose
+  /// [`Pokemon::base_experience`] is `base_experience`.
+  ///
+  /// By default, the victor is at the same level, is not traded, holds no
+  /// Lucky Egg, and is the only participant.
+  pub fn new(base_experience: u32, level: u8) -> Self {
+    Self {
+      base_experience,
+      level,
+      victor_level: level,
+      trainer: false,
+      traded: false,
+      international: false,
+      lucky_egg: false,
+      participants: 1,
+    }
+  }
+
+  /// Creates a new [`ExpGain`] for defeating a wild `pokemon` at `level`.
+  pub fn for_pokemon(pokemon: &Pokemon, level: u8) -> Self {
+    Self::new(pokemon.base_experience, level)
+  }
+
+  /// Sets the level of the Pokemon gaining experience.
+  #[inline]
+  pub fn victor_level(mut self, level: u8) -> Self {
+    self.victor_level = level;
+    self
+  }
+
+  /// Sets whether the defeated Pokemon belonged to a trainer.
+  #[inline]
+  pub fn trainer(mut self, trainer: bool) -> Self {
+    self.trainer = trainer;
+    self
+  }
+
+  /// Sets whether the Pokemon gaining experience was obtained in a trade.
+  #[inline]
+  pub fn traded(mut self, traded: bool) -> Self {
+    self.traded = traded;
+    self
+  }
+
+  /// Sets whether the Pokemon gaining experience was traded from a game in
+  /// another language, which grants a larger bonus from Generation IV
+  /// onward.
+  #[inline]
+  pub fn international(mut self, international: bool) -> Self {
+    self.international = international;
+    self
+  }
+
+  /// Sets whether the Pokemon gaining experience holds a Lucky Egg.
+  #[inline]
+  pub fn lucky_egg(mut self, lucky_egg: bool) -> Self {
+    self.lucky_egg = lucky_egg;
+    self
+  }
+
+  /// Sets the number of Pokemon the experience is split between.
+  ///
+  /// Values less than one are treated as one.
+  #[inline]
+  pub fn participants(mut self, participants: u32) -> Self {
+    self.participants = participants;
+    self
+  }
+
+  /// Computes the experience gained under `formula`.
+  pub fn compute(&self, formula: Formula) -> u32 {
+    let b = self.base_experience as f64;
+    let level = self.level as f64;
+    let victor = self.victor_level as f64;
+    let s = self.participants.max(1) as f64;
+
+    let a = if self.trainer && formula != Formula::Modern {
+      1.5
+    } else {
+      1.0
+    };
+    let t = match (self.traded, self.international) {
+      (false, _) => 1.0,
+      (true, false) => 1.5,
+      (true, true) => 1.7,
+    };
+    let e = if self.lucky_egg { 1.5 } else { 1.0 };
+
+    let exp = match formula {
+      Formula::Flat => (a * t * e * b * level / (7.0 * s)).floor(),
+      Formula::Scaled | Formula::Modern => {
+        let scale = (2.0 * level + 10.0) / (level + victor + 10.0);
+        let exp = (a * b * level / (5.0 * s) * scale.powf(2.5)).floor() + 1.0;
+        (exp * t * e).floor()
+      }
+    };
+    exp as u32
+  }
+}
@@ -0,0 +1,287 @@
+//! Reverse stat calculation, which infers a Pokemon's IVs from its stats.
+
+use std::ops::RangeInclusive;
+
+use crate::calc::stats::Calculator;
+use crate::calc::stats::Error;
+use crate::calc::stats::Formula;
+use crate::calc::stats::Spread;
+use crate::calc::stats::StatTable;
+use crate::model::nature::Characteristic;
+use crate::model::Nature;
+use crate::model::Pokemon;
+use crate::model::StatName;
+
+/// A set of IVs, as a bitset; bit `n` is set if `n` is a possible IV.
+type IvSet = u32;
+
+/// The set of every odd IV.
+const ODD: IvSet = 0xaaaa_aaaa;
+
+/// A solver for the IVs of an individual Pokemon, given the stats it has at
+/// one or more levels.
+///
+/// Every observation rules out IVs that could not have produced it, so the
+/// possible IVs narrow as more observations are made. Under
+/// [`Formula::Classic`], the DVs are also narrowed jointly: both Special stats
+/// share one DV, and the HP DV is made of the lowest bits of the others.
+///
+/// ```
+/// # use pkmn::calc::ivs::IvSolver;
+/// # use pkmn::calc::stats::StatTable;
+/// # use pkmn::model::StatName;
+/// # fn main() -> Result<(), pkmn::calc::stats::Error> {
+/// // Garchomp's base stats.
+/// let base = StatTable {
+///   hp: 108,
+///   attack: 130,
+///   defense: 95,
+///   sp_attack: 80,
+///   sp_defense: 85,
+///   speed: 102,
+/// };
+/// let mut solver = IvSolver::new(base);
+/// solver.observe(
+///   50,
+///   &StatTable {
+///     hp: 183,
+///     attack: 150,
+///     defense: 115,
+///     sp_attack: 100,
+///     sp_defense: 105,
+///     speed: 122,
+///   },
+/// )?;
+/// assert_eq!(solver.range(StatName::HitPoints), Some(30..=31));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct IvSolver {
+  base: StatTable<u32>,
+  formula: Formula,
+  nature: Option<Nature>,
+  evs: StatTable<u16>,
+  characteristic: Option<Characteristic>,
+  possible: StatTable<IvSet>,
+}
+
+impl IvSolver {
+  /// Creates a new [`IvSolver`] for a Pokemon with the given base stats,
+  /// which uses [`Formula::Modern`], a neutral nature and no EVs.
+  pub fn new(base: StatTable<u32>) -> Self {
+    Self {
+      base,
+      formula: Formula::Modern,
+      nature: None,
+      evs: StatTable::default(),
+      characteristic: None,
+      possible: StatTable::from_fn(|_| IvSet::MAX),
+    }
+  }
+
+  /// Creates a new [`IvSolver`] for an individual of `pokemon`.
+  pub fn for_pokemon(pokemon: &Pokemon) -> Self {
+    Self::new(StatTable::base_stats_of(pokemon))
+  }
+
+  /// Sets the formula used to compute stats.
+  #[inline]
+  pub fn formula(mut self, formula: Formula) -> Self {
+    self.formula = formula;
+    self
+  }
+
+  /// Sets the Pokemon's nature.
+  #[inline]
+  pub fn nature(mut self, nature: &Nature) -> Self {
+    self.nature = Some(nature.clone());
+    self
+  }
+
+  /// Sets the Pokemon's EVs, or whatever effort values `formula` uses.
+  ///
+  /// These apply to all later observations. Under [`Formula::Classic`], the
+  /// Special stat experience is read from `evs.sp_attack`.
+  #[inline]
+  pub fn evs(mut self, evs: StatTable<u16>) -> Self {
+    self.evs = evs;
+    self
+  }
+
+  /// Sets the Pokemon's [`Characteristic`], which constrains which of its
+  /// IVs is highest and what that IV may be.
+  #[inline]
+  pub fn characteristic(mut self, characteristic: &Characteristic) -> Self {
+    self.characteristic = Some(characteristic.clone());
+    self.narrow();
+    self
+  }
+
+  /// Records that the Pokemon has `stats` at `level`, ruling out any IVs that
+  /// would produce other stats.
+  pub fn observe(
+    &mut self,
+    level: u8,
+    stats: &StatTable<u32>,
+  ) -> Result<(), Error> {
+    let mut calc = Calculator::new(self.formula, level);
+    if let Some(nature) = &self.nature {
+      calc = calc.nature(nature);
+    }
+
+    for (stat, &observed) in stats.iter() {
+      let base = *self.base.get(stat).unwrap();
+      let effort = match (self.formula, stat) {
+        (Formula::Classic, StatName::SpDefense) => self.evs.sp_attack,
+        _ => *self.evs.get(stat).unwrap(),
+      };
+      let mut fits = 0;
+      for iv in 0..=self.formula.max_iv() {
+        if calc.stat(stat, base, Spread::new(iv, effort))? == observed {
+          fits |= 1 << iv;
+        }
+      }
+      *self.possible.get_mut(stat).unwrap() &= fits;
+    }
+    self.narrow();
+    Ok(())
+  }
+
+  /// Returns every IV still possible for each stat.
+  ///
+  /// If a stat has no possible IVs, the observations contradict each other
+  /// or the solver's settings.
+  pub fn possible(&self) -> StatTable<Vec<u8>> {
+    let max = self.formula.max_iv();
+    self
+      .possible
+      .map(|_, &set| (0..=max).filter(|&iv| set & 1 << iv != 0).collect())
+  }
+
+  /// Returns the smallest range containing every IV still possible for
+  /// `stat`, or `None` if there are none or `stat` is battle-only.
+  pub fn range(&self, stat: StatName) -> Option<RangeInclusive<u8>> {
+    let set = *self.possible.get(stat)? & self.all();
+    if set == 0 {
+      return None;
+    }
+    let low = set.trailing_zeros() as u8;
+    let high = (IvSet::BITS - 1 - set.leading_zeros()) as u8;
+    Some(low..=high)
+  }
+
+  /// Returns [`IvSolver::range()`] for every stat.
+  pub fn ranges(&self) -> StatTable<Option<RangeInclusive<u8>>> {
+    StatTable::from_fn(|stat| self.range(stat))
+  }
+
+  /// Returns the set of every IV valid under the solver's formula.
+  fn all(&self) -> IvSet {
+    IvSet::MAX >> (IvSet::BITS - 1 - self.formula.max_iv() as u32)
+  }
+
+  /// Applies the constraints between IVs until none of them rule out
+  /// anything more.
+  fn narrow(&mut self) {
+    loop {
+      let before = self.possible;
+      self.link_dvs();
+      self.apply_characteristic();
+      if self.possible == before {
+        return;
+      }
+    }
+  }
+
+  /// Under [`Formula::Classic`], applies the constraints between DVs: both
+  /// Special stats share one DV, and the HP DV is made of the lowest bits of
+  /// the others, as computed by `stats::classic_hp_dv()`.
+  fn link_dvs(&mut self) {
+    if self.formula != Formula::Classic {
+      return;
+    }
+    let p = &mut self.possible;
+    p.sp_attack &= p.sp_defense;
+    p.sp_defense = p.sp_attack;
+
+    // Each bit of the HP DV comes from the lowest bit of one other DV.
+    let sources = [p.attack, p.defense, p.speed, p.sp_attack];
+    let bits = [8, 4, 2, 1];
+    let hp = (0..=Formula::Classic.max_iv())
+      .filter(|&dv| p.hp & 1 << dv != 0)
+      .filter(|&dv| {
+        let matching = |(&set, &bit)| set & parity(dv & bit != 0) != 0;
+        sources.iter().zip(&bits).all(matching)
+      })
+      .fold(0, |set, dv| set | 1 << dv);
+    p.hp = hp;
+
+    // Conversely, each of those DVs must have a lowest bit that some
+    // remaining HP DV calls for.
+    let allowed = |bit: u8| {
+      (0..=Formula::Classic.max_iv())
+        .filter(|&dv| hp & 1 << dv != 0)
+        .fold(0, |set, dv| set | parity(dv & bit != 0))
+    };
+    p.attack &= allowed(8);
+    p.defense &= allowed(4);
+    p.speed &= allowed(2);
+    p.sp_attack &= allowed(1);
+    p.sp_defense = p.sp_attack;
+  }
+
+  /// Applies the constraints imposed by the characteristic, if any.
+  fn apply_characteristic(&mut self) {
+    let characteristic = match &self.characteristic {
+      Some(c) => c,
+      None => return,
+    };
+    let highest = match characteristic.highest_stat.variant() {
+      Some(stat) if self.possible.get(stat).is_some() => stat,
+      _ => return,
+    };
+
+    let values = characteristic
+      .possible_values
+      .iter()
+      .filter(|&&iv| iv < IvSet::BITS)
+      .fold(0, |set, &iv| set | 1 << iv);
+    let all = self.all();
+    let highest_set = self.possible.get_mut(highest).unwrap();
+    *highest_set &= values & all;
+
+    // Every other IV is at most the highest one; ties are broken by the
+    // Pokemon's personality value, so they cannot be ruled out.
+    let ceiling = match *highest_set {
+      0 => return,
+      set => IvSet::BITS - 1 - set.leading_zeros(),
+    };
+    let mut floor = 0;
+    for (stat, set) in self.possible.iter() {
+      let set = set & all;
+      if stat != highest && set != 0 {
+        floor = floor.max(set.trailing_zeros());
+      }
+    }
+
+    let at_most = IvSet::MAX >> (IvSet::BITS - 1 - ceiling);
+    let at_least = IvSet::MAX << floor;
+    self.possible = self.possible.map(|stat, &set| {
+      if stat == highest {
+        set & at_least
+      } else {
+        set & at_most
+      }
+    });
+  }
+}
+
+/// Returns the set of every odd IV if `odd`, or of every even one otherwise.
+fn parity(odd: bool) -> IvSet {
+  if odd {
+    ODD
+  } else {
+    !ODD
+  }
+}
@@ -0,0 +1,10 @@
+//! Calculators for game mechanics, built on top of the data model.
+
+pub mod capture;
+pub mod exp;
+pub mod ivs;
+pub mod stats;
+pub mod types;
+
+pub use types::Matchups;
+pub use types::TypeChart;
@@ -0,0 +1,405 @@
+//! Stat calculation, which turns a Pokemon's base stats into the stats of a
+//! particular individual.
+//!
+//! See <https://bulbapedia.bulbagarden.net/wiki/Stat> for the formulas used.
+
+use crate::model::GenerationName;
+use crate::model::Nature;
+use crate::model::Pokemon;
+use crate::model::StatName;
+
+#[cfg(doc)]
+use crate::model::species::BaseStat;
+
+/// The highest level a Pokemon can reach.
+pub const MAX_LEVEL: u8 = 100;
+
+/// The maximum number of EVs a Pokemon can have across all of its stats.
+pub const MAX_TOTAL_EVS: u32 = 510;
+
+/// The six stats that every Pokemon has outside of battle, in ID order.
+const PERMANENT: [StatName; 6] = [
+  StatName::HitPoints,
+  StatName::Attack,
+  StatName::Defense,
+  StatName::SpAttack,
+  StatName::SpDefense,
+  StatName::Speed,
+];
+
+/// A value for each of the six stats every Pokemon has outside of battle.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+pub struct StatTable<T> {
+  pub hp: T,
+  pub attack: T,
+  pub defense: T,
+  pub sp_attack: T,
+  pub sp_defense: T,
+  pub speed: T,
+}
+
+impl<T> StatTable<T> {
+  /// Creates a new [`StatTable`] by calling `f` on each stat.
+  pub fn from_fn(mut f: impl FnMut(StatName) -> T) -> Self {
+    Self {
+      hp: f(StatName::HitPoints),
+      attack: f(StatName::Attack),
+      defense: f(StatName::Defense),
+      sp_attack: f(StatName::SpAttack),
+      sp_defense: f(StatName::SpDefense),
+      speed: f(StatName::Speed),
+    }
+  }
+
+  /// Returns the value for `stat`, or `None` if it is a battle-only stat,
+  /// such as accuracy.
+  pub fn get(&self, stat: StatName) -> Option<&T> {
+    match stat {
+      StatName::HitPoints => Some(&self.hp),
+      StatName::Attack => Some(&self.attack),
+      StatName::Defense => Some(&self.defense),
+      StatName::SpAttack => Some(&self.sp_attack),
+      StatName::SpDefense => Some(&self.sp_defense),
+      StatName::Speed => Some(&self.speed),
+      _ => None,
+    }
+  }
+
+  /// Returns the value for `stat` mutably, or `None` if it is a battle-only
+  /// stat, such as accuracy.
+  pub fn get_mut(&mut self, stat: StatName) -> Option<&mut T> {
+    match stat {
+      StatName::HitPoints => Some(&mut self.hp),
+      StatName::Attack => Some(&mut self.attack),
+      StatName::Defense => Some(&mut self.defense),
+      StatName::SpAttack => Some(&mut self.sp_attack),
+      StatName::SpDefense => Some(&mut self.sp_defense),
+      StatName::Speed => Some(&mut self.speed),
+      _ => None,
+    }
+  }
+
+  /// Returns an iterator over each stat and its value, in ID order.
+  pub fn iter(&self) -> impl Iterator<Item = (StatName, &T)> + '_ {
+    PERMANENT.iter().map(move |&s| (s, self.get(s).unwrap()))
+  }
+
+  /// Applies `f` to each value in this table.
+  pub fn map<U>(&self, mut f: impl FnMut(StatName, &T) -> U) -> StatTable<U> {
+    StatTable::from_fn(|s| f(s, self.get(s).unwrap()))
+  }
+}
+
+impl<T> From<[T; 6]> for StatTable<T> {
+  /// Creates a new [`StatTable`] from the values of each stat, in ID order.
+  fn from([hp, attack, defense, sp_attack, sp_defense, speed]: [T; 6]) -> Self {
+    Self {
+      hp,
+      attack,
+      defense,
+      sp_attack,
+      sp_defense,
+      speed,
+    }
+  }
+}
+
+impl StatTable<u32> {
+  /// Returns the base stats of `pokemon`.
+  ///
+  /// Stats missing from [`Pokemon::stats`] are zero.
+  pub fn base_stats_of(pokemon: &Pokemon) -> Self {
+    let mut table = Self::default();
+    for base in &pokemon.stats {
+      let slot = base.stat.variant().and_then(|s| table.get_mut(s));
+      if let Some(slot) = slot {
+        *slot = base.base_stat;
+      }
+    }
+    table
+  }
+}
+
+/// A formula for computing stats, which depends on the game.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Formula {
+  /// The formula used in Generations I and II.
+  ///
+  /// Stats are computed from DVs, between 0 and 15, and stat experience,
+  /// between 0 and 65535. Natures do not exist yet.
+  Classic,
+  /// The formula used from Generation III onward.
+  ///
+  /// Stats are computed from IVs, between 0 and 31, and EVs, between 0 and
+  /// 255, of which a Pokemon may have at most 510 in total.
+  Modern,
+  /// The formula used in Let's Go, Pikachu! and Let's Go, Eevee!.
+  ///
+  /// Stats are computed from IVs, between 0 and 31, and AVs, between 0 and
+  /// 200, which are added directly to the stat; EVs are not used. The bonus
+  /// granted by friendship is not taken into account.
+  LetsGo,
+}
+
+impl Formula {
+  /// Returns the formula used by the main series games of `generation`.
+  pub fn for_generation(generation: GenerationName) -> Self {
+    if generation <= GenerationName::II {
+      Self::Classic
+    } else {
+      Self::Modern
+    }
+  }
+
+  /// Returns the largest valid IV (or DV) under this formula.
+  pub fn max_iv(self) -> u8 {
+    match self {
+      Self::Classic => 15,
+      Self::Modern | Self::LetsGo => 31,
+    }
+  }
+
+  /// Returns the largest valid effort value for a single stat under this
+  /// formula.
+  pub fn max_effort(self) -> u16 {
+    match self {
+      Self::Classic => u16::MAX,
+      Self::Modern => 255,
+      Self::LetsGo => 200,
+    }
+  }
+}
+
+/// The values that set an individual Pokemon's stat apart from those of
+/// others of its kind.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Spread {
+  /// The stat's individual value: an IV, or a DV under [`Formula::Classic`].
+  pub iv: u8,
+  /// The effort put into the stat: EVs under [`Formula::Modern`], stat
+  /// experience under [`Formula::Classic`], and AVs under
+  /// [`Formula::LetsGo`].
+  pub effort: u16,
+}
+
+impl Spread {
+  /// Creates a new [`Spread`].
+  pub fn new(iv: u8, effort: u16) -> Self {
+    Self { iv, effort }
+  }
+}
+
+/// An error returned when computing stats from invalid inputs.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+  /// The level is not between 1 and [`MAX_LEVEL`].
+  #[error("level {0} is out of range")]
+  Level(u8),
+
+  /// The stat is only used in battle, so it cannot be computed.
+  #[error("{0:?} is a battle-only stat")]
+  BattleOnly(StatName),
+
+  /// An IV is too large for the formula.
+  #[error("{stat:?} IV of {value} exceeds the maximum of {max}")]
+  Iv {
+    /// The stat with the bad IV.
+    stat: StatName,
+    /// The offending IV.
+    value: u8,
+    /// The largest valid IV.
+    max: u8,
+  },
+
+  /// An effort value is too large for the formula.
+  #[error("{stat:?} effort of {value} exceeds the maximum of {max}")]
+  Effort {
+    /// The stat with the bad effort value.
+    stat: StatName,
+    /// The offending effort value.
+    value: u16,
+    /// The largest valid effort value.
+    max: u16,
+  },
+
+  /// The EVs across all stats add up to more than [`MAX_TOTAL_EVS`].
+  #[error("{0} total EVs exceed the maximum of {}", MAX_TOTAL_EVS)]
+  TotalEvs(u32),
+}
+
+/// A stat calculator for Pokemon at a particular level.
+///
+/// ```
+/// # use pkmn::calc::stats::*;
+/// # use pkmn::model::StatName;
+/// // A level 78 Garchomp with 24 IVs and 74 EVs in HP.
+/// let calc = Calculator::new(Formula::Modern, 78);
+/// let hp = calc.stat(StatName::HitPoints, 108, Spread::new(24, 74));
+/// assert_eq!(hp, Ok(289));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Calculator {
+  formula: Formula,
+  level: u8,
+  raised: Option<StatName>,
+  lowered: Option<StatName>,
+}
+
+impl Calculator {
+  /// Creates a new [`Calculator`] for Pokemon at `level`, with a neutral
+  /// nature.
+  pub fn new(formula: Formula, level: u8) -> Self {
+    Self {
+      formula,
+      level,
+      raised: None,
+      lowered: None,
+    }
+  }
+
+  /// Sets the nature whose [`Nature::increases`] and [`Nature::decreases`]
+  /// are applied; [`Formula::Classic`] ignores it.
+  #[inline]
+  pub fn nature(mut self, nature: &Nature) -> Self {
+    self.raised = PERMANENT.iter().copied().find(|&s| nature.raises(s));
+    self.lowered = PERMANENT.iter().copied().find(|&s| nature.lowers(s));
+    self
+  }
+
+  /// Returns the formula this calculator uses.
+  pub fn formula(&self) -> Formula {
+    self.formula
+  }
+
+  /// Returns the level this calculator computes stats at.
+  pub fn level(&self) -> u8 {
+    self.level
+  }
+
+  /// Computes a single stat out of its base value, as given by
+  /// [`BaseStat::base_stat`], and `spread`.
+  ///
+  /// A base HP of 1, which only Shedinja has, always results in 1 HP.
+  pub fn stat(
+    &self,
+    stat: StatName,
+    base: u32,
+    spread: Spread,
+  ) -> Result<u32, Error> {
+    self.validate(stat, spread)?;
+
+    let level = self.level as u32;
+    let iv = spread.iv as u32;
+    let effort = spread.effort as u32;
+    let (scaled, bonus) = match self.formula {
+      Formula::Classic => {
+        ((base + iv) * 2 + isqrt_ceil(effort).min(255) / 4, 0)
+      }
+      Formula::Modern => (2 * base + iv + effort / 4, 0),
+      Formula::LetsGo => (2 * base + iv, effort),
+    };
+    let value = scaled * level / 100;
+
+    if stat == StatName::HitPoints {
+      if base == 1 {
+        return Ok(1);
+      }
+      return Ok(value + level + 10 + bonus);
+    }
+
+    let value = value + 5;
+    let value = match self.formula {
+      Formula::Classic => value,
+      _ if self.raised == Some(stat) => value * 110 / 100,
+      _ if self.lowered == Some(stat) => value * 90 / 100,
+      _ => value,
+    };
+    Ok(value + bonus)
+  }
+
+  /// Computes every stat out of `base` stats, such as those returned by
+  /// [`StatTable::base_stats_of()`], and `spreads`.
+  ///
+  /// Under [`Formula::Classic`], the HP DV is derived from the other DVs with
+  /// [`classic_hp_dv()`], and both Special stats use the Special DV and stat
+  /// experience in `spreads.sp_attack`.
+  pub fn stats(
+    &self,
+    base: &StatTable<u32>,
+    spreads: &StatTable<Spread>,
+  ) -> Result<StatTable<u32>, Error> {
+    let mut spreads = *spreads;
+    match self.formula {
+      Formula::Classic => {
+        spreads.hp.iv = classic_hp_dv(&spreads);
+        spreads.sp_defense = spreads.sp_attack;
+      }
+      Formula::Modern => {
+        let total = spreads.iter().map(|(_, s)| s.effort as u32).sum();
+        if total > MAX_TOTAL_EVS {
+          return Err(Error::TotalEvs(total));
+        }
+      }
+      Formula::LetsGo => {}
+    }
+
+    let mut stats = StatTable::default();
+    for (stat, spread) in spreads.iter() {
+      let value = self.stat(stat, *base.get(stat).unwrap(), *spread)?;
+      *stats.get_mut(stat).unwrap() = value;
+    }
+    Ok(stats)
+  }
+
+  fn validate(&self, stat: StatName, spread: Spread) -> Result<(), Error> {
+    if self.level == 0 || self.level > MAX_LEVEL {
+      return Err(Error::Level(self.level));
+    }
+    if !PERMANENT.contains(&stat) {
+      return Err(Error::BattleOnly(stat));
+    }
+
+    let max = self.formula.max_iv();
+    if spread.iv > max {
+      return Err(Error::Iv {
+        stat,
+        value: spread.iv,
+        max,
+      });
+    }
+    let max = self.formula.max_effort();
+    if spread.effort > max {
+      return Err(Error::Effort {
+        stat,
+        value: spread.effort,
+        max,
+      });
+    }
+    Ok(())
+  }
+}
+
+/// Returns the HP DV implied by the Attack, Defense, Speed and Special DVs in
+/// `dvs`, as in Generations I and II.
+///
+/// Each of those DVs contributes its lowest bit, with Attack's being the most
+/// significant. The Special DV is read from `dvs.sp_attack`.
+pub fn classic_hp_dv(dvs: &StatTable<Spread>) -> u8 {
+  (dvs.attack.iv & 1) << 3
+    | (dvs.defense.iv & 1) << 2
+    | (dvs.speed.iv & 1) << 1
+    | (dvs.sp_attack.iv & 1)
+}
+
+/// Computes the square root of `x`, rounded up.
+fn isqrt_ceil(x: u32) -> u32 {
+  let mut root = (x as f64).sqrt() as u32;
+  while root * root > x {
+    root -= 1;
+  }
+  while root * root < x {
+    root += 1;
+  }
+  root
+}
@@ -0,0 +1,180 @@
+//! Type effectiveness, as described by the type chart.
+
+use crate::model::GenerationName;
+use crate::model::NamedResource;
+use crate::model::Type;
+use crate::model::TypeName;
+
+#[cfg(doc)]
+use crate::model::ty::Relations;
+
+/// A type chart, which describes how effective moves of each [`Type`] are
+/// against Pokemon of each [`Type`].
+///
+/// A chart is built out of the [`Relations`] of every type; the "???" and
+/// Shadow types, as well as types `pkmn` does not know the name of, are
+/// ignored.
+///
+/// ```no_run
+/// # use pkmn::calc::TypeChart;
+/// # use pkmn::model::*;
+/// # fn main() -> Result<(), pkmn::api::Error> {
+/// let api = pkmn::Api::new();
+/// let types = api.all::<Type>(20).collect::<Result<Vec<_>, _>>()?;
+/// let chart = TypeChart::new(&types);
+///
+/// let dragonite = [TypeName::Dragon, TypeName::Flying];
+/// assert_eq!(chart.multiplier(TypeName::Ice, &dragonite), 4.0);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TypeChart {
+  types: Vec<TypeName>,
+  // Row-major: `matrix[attacking * types.len() + defending]`.
+  matrix: Vec<f64>,
+}
+
+impl TypeChart {
+  /// Builds the current type chart out of `types`, which may be [`Type`]s,
+  /// references to them, or `Arc`s of them.
+  pub fn new<T: AsRef<Type>>(types: impl IntoIterator<Item = T>) -> Self {
+    Self::build(types, None)
+  }
+
+  /// Builds the type chart for `generation` out of `types`.
+  ///
+  /// Types introduced after `generation`, such as Fairy before Generation VI,
+  /// are left out, and type relations that have since changed, such as Ghost
+  /// moves having no effect on Psychic Pokemon in Generation I, are used in
+  /// place of the current ones.
+  pub fn for_generation<T: AsRef<Type>>(
+    types: impl IntoIterator<Item = T>,
+    generation: GenerationName,
+  ) -> Self {
+    Self::build(types, Some(generation))
+  }
+
+  fn build<T: AsRef<Type>>(
+    types: impl IntoIterator<Item = T>,
+    generation: Option<GenerationName>,
+  ) -> Self {
+    let mut types = types
+      .into_iter()
+      .filter(|ty| {
+        let ty = ty.as_ref();
+        let introduced = ty.generation.variant();
+        let in_generation = match (generation, introduced) {
+          (None, _) => true,
+          (Some(g), Some(introduced)) => introduced <= g,
+          (Some(_), None) => false,
+        };
+        in_generation
+          && !matches!(
+            ty.name.variant(),
+            None | Some(TypeName::Unknown) | Some(TypeName::Shadow)
+          )
+      })
+      .collect::<Vec<_>>();
+    types.sort_by_key(|ty| ty.as_ref().id);
+
+    let names = types
+      .iter()
+      .filter_map(|ty| ty.as_ref().name.variant())
+      .collect::<Vec<_>>();
+    let mut chart = Self {
+      matrix: vec![1.0; names.len() * names.len()],
+      types: names,
+    };
+
+    for ty in &types {
+      let ty = ty.as_ref();
+      let relations = match generation {
+        Some(g) => ty.relations_in(g),
+        None => &ty.relations,
+      };
+      let attacking = ty.name.variant().unwrap();
+      chart.relate(attacking, &relations.no_effect, 0.0);
+      chart.relate(attacking, &relations.not_very_effective, 0.5);
+      chart.relate(attacking, &relations.super_effective, 2.0);
+    }
+    chart
+  }
+
+  /// Records that moves of type `attacking` deal `multiplier` damage to each
+  /// of `defending`.
+  fn relate(
+    &mut self,
+    attacking: TypeName,
+    defending: &[NamedResource<Type>],
+    multiplier: f64,
+  ) {
+    let n = self.types.len();
+    let a = self.index(attacking).unwrap();
+    for d in defending {
+      if let Some(d) = d.variant().and_then(|d| self.index(d)) {
+        self.matrix[a * n + d] = multiplier;
+      }
+    }
+  }
+
+  fn index(&self, ty: TypeName) -> Option<usize> {
+    self.types.iter().position(|&t| t == ty)
+  }
+
+  /// Returns the types in this chart, ordered by ID.
+  pub fn types(&self) -> &[TypeName] {
+    &self.types
+  }
+
+  /// Returns the damage multiplier of a move of type `attacking` against a
+  /// Pokemon of type `defending`.
+  ///
+  /// Types that are not part of this chart are treated as neutral.
+  pub fn effectiveness(&self, attacking: TypeName, defending: TypeName) -> f64 {
+    match (self.index(attacking), self.index(defending)) {
+      (Some(a), Some(d)) => self.matrix[a * self.types.len() + d],
+      _ => 1.0,
+    }
+  }
+
+  /// Returns the damage multiplier of a move of type `attacking` against a
+  /// Pokemon with all of the types in `defending`.
+  pub fn multiplier(&self, attacking: TypeName, defending: &[TypeName]) -> f64 {
+    defending
+      .iter()
+      .map(|&d| self.effectiveness(attacking, d))
+      .product()
+  }
+
+  /// Returns how moves of every type in this chart fare against a Pokemon with
+  /// all of the types in `defending`.
+  pub fn matchups(&self, defending: &[TypeName]) -> Matchups {
+    let mut matchups = Matchups::default();
+    for &attacking in &self.types {
+      let x = self.multiplier(attacking, defending);
+      if x == 0.0 {
+        matchups.immunities.push(attacking);
+      } else if x < 1.0 {
+        matchups.resistances.push((attacking, x));
+      } else if x > 1.0 {
+        matchups.weaknesses.push((attacking, x));
+      }
+    }
+    matchups
+  }
+}
+
+/// How moves of every type fare against a particular combination of defending
+/// types, as returned by [`TypeChart::matchups()`].
+///
+/// Each list is ordered by type ID.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Matchups {
+  /// Move types that deal extra damage, with their multipliers.
+  pub weaknesses: Vec<(TypeName, f64)>,
+  /// Move types that deal reduced damage, with their multipliers.
+  pub resistances: Vec<(TypeName, f64)>,
+  /// Move types that have no effect.
+  pub immunities: Vec<TypeName>,
+}
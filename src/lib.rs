@@ -3,6 +3,7 @@
 #![deny(warnings, missing_docs, unused)]
 
 pub mod api;
+pub mod calc;
 pub mod model;
 
 pub use api::Api;
@@ -4,13 +4,15 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::api::Endpoint;
+use crate::model::resource::NameOf;
+use crate::model::resource::NamedResource;
 use crate::model::resource::Resource;
 use crate::model::species::Pokemon;
 use crate::model::text::Effect;
 use crate::model::text::Erratum;
 use crate::model::text::Localized;
 use crate::model::version::Generation;
-use crate::model::version::Version;
+use crate::model::version::VersionGroup;
 
 text_field!(flavor_text);
 
@@ -20,7 +22,7 @@ pub struct Ability {
   /// This ability's numeric ID.
   pub id: u32,
   /// This ability's API name.
-  pub name: String,
+  pub name: NameOf<Self>,
   /// The name of this ability in various languages.
   #[serde(rename = "names")]
   pub localized_names: Localized,
@@ -33,12 +35,12 @@ pub struct Ability {
   pub errata: Vec<Erratum>,
   /// Flavor text for this ability in various languages.
   #[serde(rename = "flavor_text_entries")]
-  pub flavor_text: Localized<FlavorText, Version>,
+  pub flavor_text: Localized<FlavorText, VersionGroup>,
 
   /// Whether this ability is actually used in main-series games.
   pub is_main_series: bool,
   /// The generation this ability was introduced in.
-  pub generation: Resource<Generation>,
+  pub generation: NamedResource<Generation>,
 
   /// Pokemon which can have this ability.
   #[serde(rename = "pokemon")]
@@ -61,3 +63,336 @@ pub struct User {
 impl Endpoint for Ability {
   const NAME: &'static str = "ability";
 }
+
+well_known! {
+  /// A name for an [`Ability`].
+  ///
+  /// This covers every ability in the core series games, through Generation
+  /// IX.
+  #[allow(missing_docs)]
+  pub enum AbilityName for Ability {
+    // Generation III.
+    Stench => "stench",
+    Drizzle => "drizzle",
+    SpeedBoost => "speed-boost",
+    BattleArmor => "battle-armor",
+    Sturdy => "sturdy",
+    Damp => "damp",
+    Limber => "limber",
+    SandVeil => "sand-veil",
+    Static => "static",
+    VoltAbsorb => "volt-absorb",
+    WaterAbsorb => "water-absorb",
+    Oblivious => "oblivious",
+    CloudNine => "cloud-nine",
+    CompoundEyes => "compound-eyes",
+    Insomnia => "insomnia",
+    ColorChange => "color-change",
+    Immunity => "immunity",
+    FlashFire => "flash-fire",
+    ShieldDust => "shield-dust",
+    OwnTempo => "own-tempo",
+    SuctionCups => "suction-cups",
+    Intimidate => "intimidate",
+    ShadowTag => "shadow-tag",
+    RoughSkin => "rough-skin",
+    WonderGuard => "wonder-guard",
+    Levitate => "levitate",
+    EffectSpore => "effect-spore",
+    Synchronize => "synchronize",
+    ClearBody => "clear-body",
+    NaturalCure => "natural-cure",
+    LightningRod => "lightning-rod",
+    SereneGrace => "serene-grace",
+    SwiftSwim => "swift-swim",
+    Chlorophyll => "chlorophyll",
+    Illuminate => "illuminate",
+    Trace => "trace",
+    HugePower => "huge-power",
+    PoisonPoint => "poison-point",
+    InnerFocus => "inner-focus",
+    MagmaArmor => "magma-armor",
+    WaterVeil => "water-veil",
+    MagnetPull => "magnet-pull",
+    Soundproof => "soundproof",
+    RainDish => "rain-dish",
+    SandStream => "sand-stream",
+    Pressure => "pressure",
+    ThickFat => "thick-fat",
+    EarlyBird => "early-bird",
+    FlameBody => "flame-body",
+    RunAway => "run-away",
+    KeenEye => "keen-eye",
+    HyperCutter => "hyper-cutter",
+    Pickup => "pickup",
+    Truant => "truant",
+    Hustle => "hustle",
+    CuteCharm => "cute-charm",
+    Plus => "plus",
+    Minus => "minus",
+    Forecast => "forecast",
+    StickyHold => "sticky-hold",
+    ShedSkin => "shed-skin",
+    Guts => "guts",
+    MarvelScale => "marvel-scale",
+    LiquidOoze => "liquid-ooze",
+    Overgrow => "overgrow",
+    Blaze => "blaze",
+    Torrent => "torrent",
+    Swarm => "swarm",
+    RockHead => "rock-head",
+    Drought => "drought",
+    ArenaTrap => "arena-trap",
+    VitalSpirit => "vital-spirit",
+    WhiteSmoke => "white-smoke",
+    PurePower => "pure-power",
+    ShellArmor => "shell-armor",
+    AirLock => "air-lock",
+
+    // Generation IV.
+    TangledFeet => "tangled-feet",
+    MotorDrive => "motor-drive",
+    Rivalry => "rivalry",
+    Steadfast => "steadfast",
+    SnowCloak => "snow-cloak",
+    Gluttony => "gluttony",
+    AngerPoint => "anger-point",
+    Unburden => "unburden",
+    Heatproof => "heatproof",
+    Simple => "simple",
+    DrySkin => "dry-skin",
+    Download => "download",
+    IronFist => "iron-fist",
+    PoisonHeal => "poison-heal",
+    Adaptability => "adaptability",
+    SkillLink => "skill-link",
+    Hydration => "hydration",
+    SolarPower => "solar-power",
+    QuickFeet => "quick-feet",
+    Normalize => "normalize",
+    Sniper => "sniper",
+    MagicGuard => "magic-guard",
+    NoGuard => "no-guard",
+    Stall => "stall",
+    Technician => "technician",
+    LeafGuard => "leaf-guard",
+    Klutz => "klutz",
+    MoldBreaker => "mold-breaker",
+    SuperLuck => "super-luck",
+    Aftermath => "aftermath",
+    Anticipation => "anticipation",
+    Forewarn => "forewarn",
+    Unaware => "unaware",
+    TintedLens => "tinted-lens",
+    Filter => "filter",
+    SlowStart => "slow-start",
+    Scrappy => "scrappy",
+    StormDrain => "storm-drain",
+    IceBody => "ice-body",
+    SolidRock => "solid-rock",
+    SnowWarning => "snow-warning",
+    HoneyGather => "honey-gather",
+    Frisk => "frisk",
+    Reckless => "reckless",
+    Multitype => "multitype",
+    FlowerGift => "flower-gift",
+    BadDreams => "bad-dreams",
+
+    // Generation V.
+    Pickpocket => "pickpocket",
+    SheerForce => "sheer-force",
+    Contrary => "contrary",
+    Unnerve => "unnerve",
+    Defiant => "defiant",
+    Defeatist => "defeatist",
+    CursedBody => "cursed-body",
+    Healer => "healer",
+    FriendGuard => "friend-guard",
+    WeakArmor => "weak-armor",
+    HeavyMetal => "heavy-metal",
+    LightMetal => "light-metal",
+    Multiscale => "multiscale",
+    ToxicBoost => "toxic-boost",
+    FlareBoost => "flare-boost",
+    Harvest => "harvest",
+    Telepathy => "telepathy",
+    Moody => "moody",
+    Overcoat => "overcoat",
+    PoisonTouch => "poison-touch",
+    Regenerator => "regenerator",
+    BigPecks => "big-pecks",
+    SandRush => "sand-rush",
+    WonderSkin => "wonder-skin",
+    Analytic => "analytic",
+    Illusion => "illusion",
+    Imposter => "imposter",
+    Infiltrator => "infiltrator",
+    Mummy => "mummy",
+    Moxie => "moxie",
+    Justified => "justified",
+    Rattled => "rattled",
+    MagicBounce => "magic-bounce",
+    SapSipper => "sap-sipper",
+    Prankster => "prankster",
+    SandForce => "sand-force",
+    IronBarbs => "iron-barbs",
+    ZenMode => "zen-mode",
+    VictoryStar => "victory-star",
+    Turboblaze => "turboblaze",
+    Teravolt => "teravolt",
+
+    // Generation VI.
+    AromaVeil => "aroma-veil",
+    FlowerVeil => "flower-veil",
+    CheekPouch => "cheek-pouch",
+    Protean => "protean",
+    FurCoat => "fur-coat",
+    Magician => "magician",
+    Bulletproof => "bulletproof",
+    Competitive => "competitive",
+    StrongJaw => "strong-jaw",
+    Refrigerate => "refrigerate",
+    SweetVeil => "sweet-veil",
+    StanceChange => "stance-change",
+    GaleWings => "gale-wings",
+    MegaLauncher => "mega-launcher",
+    GrassPelt => "grass-pelt",
+    Symbiosis => "symbiosis",
+    ToughClaws => "tough-claws",
+    Pixilate => "pixilate",
+    Gooey => "gooey",
+    Aerilate => "aerilate",
+    ParentalBond => "parental-bond",
+    DarkAura => "dark-aura",
+    FairyAura => "fairy-aura",
+    AuraBreak => "aura-break",
+    PrimordialSea => "primordial-sea",
+    DesolateLand => "desolate-land",
+    DeltaStream => "delta-stream",
+
+    // Generation VII.
+    Stamina => "stamina",
+    WimpOut => "wimp-out",
+    EmergencyExit => "emergency-exit",
+    WaterCompaction => "water-compaction",
+    Merciless => "merciless",
+    ShieldsDown => "shields-down",
+    Stakeout => "stakeout",
+    WaterBubble => "water-bubble",
+    Steelworker => "steelworker",
+    Berserk => "berserk",
+    SlushRush => "slush-rush",
+    LongReach => "long-reach",
+    LiquidVoice => "liquid-voice",
+    Triage => "triage",
+    Galvanize => "galvanize",
+    SurgeSurfer => "surge-surfer",
+    Schooling => "schooling",
+    Disguise => "disguise",
+    BattleBond => "battle-bond",
+    PowerConstruct => "power-construct",
+    Corrosion => "corrosion",
+    Comatose => "comatose",
+    QueenlyMajesty => "queenly-majesty",
+    InnardsOut => "innards-out",
+    Dancer => "dancer",
+    Battery => "battery",
+    Fluffy => "fluffy",
+    Dazzling => "dazzling",
+    SoulHeart => "soul-heart",
+    TanglingHair => "tangling-hair",
+    Receiver => "receiver",
+    PowerOfAlchemy => "power-of-alchemy",
+    BeastBoost => "beast-boost",
+    RksSystem => "rks-system",
+    ElectricSurge => "electric-surge",
+    PsychicSurge => "psychic-surge",
+    MistySurge => "misty-surge",
+    GrassySurge => "grassy-surge",
+    FullMetalBody => "full-metal-body",
+    ShadowShield => "shadow-shield",
+    PrismArmor => "prism-armor",
+    Neuroforce => "neuroforce",
+
+    // Generation VIII.
+    IntrepidSword => "intrepid-sword",
+    DauntlessShield => "dauntless-shield",
+    Libero => "libero",
+    BallFetch => "ball-fetch",
+    CottonDown => "cotton-down",
+    PropellerTail => "propeller-tail",
+    MirrorArmor => "mirror-armor",
+    GulpMissile => "gulp-missile",
+    Stalwart => "stalwart",
+    SteamEngine => "steam-engine",
+    PunkRock => "punk-rock",
+    SandSpit => "sand-spit",
+    IceScales => "ice-scales",
+    Ripen => "ripen",
+    IceFace => "ice-face",
+    PowerSpot => "power-spot",
+    Mimicry => "mimicry",
+    ScreenCleaner => "screen-cleaner",
+    SteelySpirit => "steely-spirit",
+    PerishBody => "perish-body",
+    WanderingSpirit => "wandering-spirit",
+    GorillaTactics => "gorilla-tactics",
+    NeutralizingGas => "neutralizing-gas",
+    PastelVeil => "pastel-veil",
+    HungerSwitch => "hunger-switch",
+    QuickDraw => "quick-draw",
+    UnseenFist => "unseen-fist",
+    CuriousMedicine => "curious-medicine",
+    Transistor => "transistor",
+    DragonsMaw => "dragons-maw",
+    ChillingNeigh => "chilling-neigh",
+    GrimNeigh => "grim-neigh",
+    AsOneGlastrier => "as-one-glastrier",
+    AsOneSpectrier => "as-one-spectrier",
+
+    // Generation IX.
+    LingeringAroma => "lingering-aroma",
+    SeedSower => "seed-sower",
+    ThermalExchange => "thermal-exchange",
+    AngerShell => "anger-shell",
+    PurifyingSalt => "purifying-salt",
+    WellBakedBody => "well-baked-body",
+    WindRider => "wind-rider",
+    GuardDog => "guard-dog",
+    RockyPayload => "rocky-payload",
+    WindPower => "wind-power",
+    ZeroToHero => "zero-to-hero",
+    Commander => "commander",
+    Electromorphosis => "electromorphosis",
+    Protosynthesis => "protosynthesis",
+    QuarkDrive => "quark-drive",
+    GoodAsGold => "good-as-gold",
+    VesselOfRuin => "vessel-of-ruin",
+    SwordOfRuin => "sword-of-ruin",
+    TabletsOfRuin => "tablets-of-ruin",
+    BeadsOfRuin => "beads-of-ruin",
+    OrichalcumPulse => "orichalcum-pulse",
+    HadronEngine => "hadron-engine",
+    Opportunist => "opportunist",
+    CudChew => "cud-chew",
+    Sharpness => "sharpness",
+    SupremeOverlord => "supreme-overlord",
+    Costar => "costar",
+    ToxicDebris => "toxic-debris",
+    ArmorTail => "armor-tail",
+    EarthEater => "earth-eater",
+    MyceliumMight => "mycelium-might",
+    MindsEye => "minds-eye",
+    SupersweetSyrup => "supersweet-syrup",
+    Hospitality => "hospitality",
+    ToxicChain => "toxic-chain",
+    EmbodyAspectTeal => "embody-aspect-teal",
+    EmbodyAspectHearthflame => "embody-aspect-hearthflame",
+    EmbodyAspectWellspring => "embody-aspect-wellspring",
+    EmbodyAspectCornerstone => "embody-aspect-cornerstone",
+    TeraShift => "tera-shift",
+    TeraShell => "tera-shell",
+    TeraformZero => "teraform-zero",
+    PoisonPuppeteer => "poison-puppeteer",
+  }
+}
@@ -34,9 +34,10 @@ pub struct Berry {
   pub size: u32,
 
   /// This berry's corresponding [`Item`].
-  pub item: Resource<Item>,
+  pub item: NamedResource<Item>,
 
   /// How many hours it takes for a berry tree to advance one stage.
+  #[serde(rename = "growth_time")]
   pub growth_rate: u32,
   /// The maximum number of berries on a fully-grown tree.
   pub max_harvest: u32,
@@ -77,7 +78,7 @@ mod flavor_and_potency {
   }
 
   pub fn serialize<S>(
-    v: &Vec<(Resource<Flavor>, u32)>,
+    v: &[(Resource<Flavor>, u32)],
     s: S,
   ) -> Result<S::Ok, S::Error>
   where
@@ -149,7 +150,7 @@ mod berry_and_potency {
   }
 
   pub fn serialize<S>(
-    v: &Vec<(Resource<Berry>, u32)>,
+    v: &[(Resource<Berry>, u32)],
     s: S,
   ) -> Result<S::Ok, S::Error>
   where
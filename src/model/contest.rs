@@ -63,8 +63,6 @@ pub struct SuperEffect {
 
   /// The number of hearts the user of the move gains.
   pub appeal: u32,
-  /// The number of hearts the previous contestant loses.
-  pub jam: u32,
 
   /// Flavor text for this ability in various languages.
   #[serde(rename = "flavor_text_entries")]
@@ -26,7 +26,7 @@ impl Percent {
 
   /// Converts this [`Percent`] into a percent value in the range `0..=100`.
   pub fn into_inner(self) -> u8 {
-    self.0.max(100)
+    self.0.min(100)
   }
 
   /// Converts this [`Percent`] into a floating-point value in the range
@@ -38,6 +38,6 @@ impl Percent {
 
 impl fmt::Display for Percent {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    write!(f, "{}%", self.0.max(100))
+    write!(f, "{}%", self.0.min(100))
   }
 }
@@ -25,9 +25,9 @@ pub struct Family {
   /// This family's numeric ID.
   pub id: u32,
   /// The item needed to breed the base stage Pokemon, if necessary.
-  pub baby_trigger_item: Option<Resource<Item>>,
+  pub baby_trigger_item: Option<NamedResource<Item>>,
   /// The base stage for this family.
-  #[serde(rename = "link")]
+  #[serde(rename = "chain")]
   pub base_stage: Stage,
 }
 
@@ -56,12 +56,12 @@ pub struct Condition {
   /// The event that triggers the evolution (such as a level-up).
   pub trigger: Resource<Trigger>,
   /// An item that can be used to directly trigger evolution.
-  pub item: Option<Resource<Item>>,
+  pub item: Option<NamedResource<Item>>,
 
   /// The gender this Pokemon must be during the trigger.
   pub gender: Option<u32>,
   /// An item that must be held during the trigger.
-  pub held_item: Option<Resource<Item>>,
+  pub held_item: Option<NamedResource<Item>>,
   /// A location evolution must be triggered at.
   pub location: Option<Resource<Location>>,
   /// Whether it must be raining during the trigger.
@@ -87,7 +87,7 @@ pub struct Condition {
 
   /// A relation between Attack and Defense required during the trigger.
   #[serde(rename = "relative_physical_stats")]
-  pub relative_stats: RelativeStats,
+  pub relative_stats: Option<RelativeStats>,
 
   /// The time of day it must be during the trigger.
   // TODO: newtype
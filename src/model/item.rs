@@ -4,21 +4,24 @@
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::api::Blob;
 use crate::api::Endpoint;
 use crate::model::evolution::Family;
 use crate::model::mov::Move;
+use crate::model::resource::NameOf;
+use crate::model::resource::NamedResource;
 use crate::model::resource::Resource;
 use crate::model::species::Pokemon;
 use crate::model::text::Effect;
 use crate::model::text::Localized;
-use crate::model::version::GameId;
-use crate::model::version::Generation;
+use crate::model::version::GenerationGameId;
 use crate::model::version::Version;
 use crate::model::version::VersionGroup;
+use crate::model::version::VersionGroupName;
 use crate::model::Percent;
 
-text_field!(flavor_text);
 text_field! {
+  text: FlavorText,
   description: Desc,
   effect: EffectText,
 }
@@ -29,16 +32,14 @@ pub struct Item {
   /// This item's numeric ID.
   pub id: u32,
   /// This item's API name.
-  pub name: String,
+  pub name: NameOf<Self>,
   /// The name of this item in various languages.
   #[serde(rename = "names")]
   pub localized_names: Localized,
 
-  /// The generation this item was introduced in.
-  pub generation: Resource<Generation>,
   /// The internal game ids for this item.
   #[serde(rename = "game_indices")]
-  pub game_ids: Vec<GameId>,
+  pub game_ids: Vec<GenerationGameId>,
 
   /// This item's attributes.
   pub attributes: Vec<Resource<Attribute>>,
@@ -58,8 +59,9 @@ pub struct Item {
   /// If present, this item can be used to produce a special baby offspring.
   ///
   /// For example, a Lax Incense can be used to breed a Wynaut from a Wobbufet.
-  pub baby_trigger_for: Option<Family>,
+  pub baby_trigger_for: Option<Resource<Family>>,
   /// Pokemon that can potentially hold this item.
+  #[serde(rename = "held_by_pokemon")]
   pub holders: Vec<Holder>,
 
   /// The power of the [`Move`] Fling when used by a Pokemon holding this item,
@@ -77,7 +79,8 @@ pub struct Item {
 /// Sprites for an [`Item`].
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Sprites {
-  // TODO
+  /// The default sprite for this item, if it has one.
+  pub default: Option<Blob>,
 }
 
 /// A [`Pokemon`] that can potentially hold a particular [`Item`].
@@ -96,7 +99,7 @@ pub struct HeldRarity {
   /// The chance that the item is being held.
   pub rarity: Percent,
   /// The version group this rarity is valid for.
-  pub version: Resource<Version>,
+  pub version: NamedResource<Version>,
 }
 
 /// A version in which an [`Item`] acts like a particular [`Tm`].
@@ -105,14 +108,85 @@ pub struct TmVersion {
   /// The TM in question.
   #[serde(rename = "machine")]
   pub tm: Resource<Tm>,
-  /// The version this TM mapping is valid for.
-  pub version: Resource<VersionGroup>,
+  /// The version group this TM mapping is valid for.
+  pub version_group: NamedResource<VersionGroup>,
+}
+
+impl Item {
+  /// Returns the TM this item acts as in `version_group`, if there is one.
+  pub fn tm_in(
+    &self,
+    version_group: VersionGroupName,
+  ) -> Option<&Resource<Tm>> {
+    find_tm(&self.tms, version_group)
+  }
+}
+
+/// Returns the TM in `tms` for `version_group`.
+pub(crate) fn find_tm(
+  tms: &[TmVersion],
+  version_group: VersionGroupName,
+) -> Option<&Resource<Tm>> {
+  tms
+    .iter()
+    .find(|tm| tm.version_group.is(version_group))
+    .map(|tm| &tm.tm)
 }
 
 impl Endpoint for Item {
   const NAME: &'static str = "item";
 }
 
+well_known! {
+  /// A name for an [`Item`].
+  ///
+  /// This covers every evolution stone, and every Poke Ball except the Hisuian
+  /// ones from Legends: Arceus.
+  #[allow(missing_docs)]
+  pub enum ItemName for Item {
+    // Poke Balls.
+    MasterBall => "master-ball",
+    UltraBall => "ultra-ball",
+    GreatBall => "great-ball",
+    PokeBall => "poke-ball",
+    SafariBall => "safari-ball",
+    NetBall => "net-ball",
+    DiveBall => "dive-ball",
+    NestBall => "nest-ball",
+    RepeatBall => "repeat-ball",
+    TimerBall => "timer-ball",
+    LuxuryBall => "luxury-ball",
+    PremierBall => "premier-ball",
+    DuskBall => "dusk-ball",
+    HealBall => "heal-ball",
+    QuickBall => "quick-ball",
+    CherishBall => "cherish-ball",
+    FastBall => "fast-ball",
+    LevelBall => "level-ball",
+    LureBall => "lure-ball",
+    HeavyBall => "heavy-ball",
+    LoveBall => "love-ball",
+    FriendBall => "friend-ball",
+    MoonBall => "moon-ball",
+    SportBall => "sport-ball",
+    ParkBall => "park-ball",
+    DreamBall => "dream-ball",
+    BeastBall => "beast-ball",
+
+    // Evolution stones.
+    FireStone => "fire-stone",
+    WaterStone => "water-stone",
+    ThunderStone => "thunder-stone",
+    LeafStone => "leaf-stone",
+    MoonStone => "moon-stone",
+    SunStone => "sun-stone",
+    ShinyStone => "shiny-stone",
+    DuskStone => "dusk-stone",
+    DawnStone => "dawn-stone",
+    IceStone => "ice-stone",
+  }
+}
+
 /// An attribute that describes an aspect of an [`Item`], such as "consumable".
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Attribute {
@@ -127,7 +201,7 @@ pub struct Attribute {
   pub descriptions: Localized<Desc>,
 
   /// Items with this attribute.
-  pub items: Vec<Resource<Item>>,
+  pub items: Vec<NamedResource<Item>>,
 }
 
 impl Endpoint for Attribute {
@@ -146,7 +220,7 @@ pub struct Category {
   pub localized_names: Localized,
 
   /// Items in this category.
-  pub items: Vec<Resource<Item>>,
+  pub items: Vec<NamedResource<Item>>,
   /// The pocket that items in the category would go into.
   pub pocket: Resource<Pocket>,
 }
@@ -184,11 +258,12 @@ pub struct Tm {
   /// This type's numeric ID.
   pub id: u32,
   /// The item corresponding to this TM.
-  pub item: Resource<Item>,
+  pub item: NamedResource<Item>,
   /// The move this TM teaches.
+  #[serde(rename = "move")]
   pub mov: Resource<Move>,
   /// The versions this TM mapping applies to.
-  pub version_group: VersionGroup,
+  pub version_group: NamedResource<VersionGroup>,
 }
 
 impl Endpoint for Tm {
@@ -206,7 +281,7 @@ pub struct FlingEffect {
   #[serde(rename = "effect_entries")]
   pub effects: Localized<EffectText>,
   /// Items with this effect.
-  pub items: Vec<Resource<Item>>,
+  pub items: Vec<NamedResource<Item>>,
 }
 
 impl Endpoint for FlingEffect {
@@ -5,14 +5,17 @@ use serde::Serialize;
 
 use crate::api::Endpoint;
 use crate::model::pokedex::Pokedex;
+use crate::model::resource::NameOf;
+use crate::model::resource::NamedResource;
 use crate::model::resource::Resource;
 use crate::model::species::Pokemon;
 use crate::model::species::Species;
 use crate::model::text::Localized;
-use crate::model::version::GameId;
 use crate::model::version::Generation;
+use crate::model::version::GenerationGameId;
 use crate::model::version::Version;
 use crate::model::version::VersionGroup;
+use crate::model::version::VersionName;
 use crate::model::Percent;
 
 /// A region, such as Kanto or Sinnoh.
@@ -21,15 +24,15 @@ pub struct Region {
   /// This region's numeric ID.
   pub id: u32,
   /// This region's API name.
-  pub name: String,
+  pub name: NameOf<Self>,
   /// The name of this region in various languages.
   #[serde(rename = "names")]
   pub localized_names: Localized,
 
-  /// The generation this region was introduced in.
-  pub main_generation: Vec<Resource<Generation>>,
+  /// The generation this region was introduced in, if any.
+  pub main_generation: Option<NamedResource<Generation>>,
   /// Version groups associated with this region.
-  pub version_groups: Vec<Resource<VersionGroup>>,
+  pub version_groups: Vec<NamedResource<VersionGroup>>,
 
   /// Locations that are part of this region.
   pub locations: Vec<Resource<Location>>,
@@ -41,6 +44,23 @@ impl Endpoint for Region {
   const NAME: &'static str = "region";
 }
 
+well_known! {
+  /// A name for a [`Region`].
+  #[allow(missing_docs)]
+  pub enum RegionName for Region {
+    Kanto => "kanto",
+    Johto => "johto",
+    Hoenn => "hoenn",
+    Sinnoh => "sinnoh",
+    Unova => "unova",
+    Kalos => "kalos",
+    Alola => "alola",
+    Galar => "galar",
+    Hisui => "hisui",
+    Paldea => "paldea",
+  }
+}
+
 /// A location within a [`Region`], such as Kanto Route 1 or Canalave City.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Location {
@@ -54,10 +74,10 @@ pub struct Location {
 
   /// The internal game ids for this location.
   #[serde(rename = "game_indices")]
-  pub game_ids: Vec<GameId>,
+  pub game_ids: Vec<GenerationGameId>,
 
-  /// The region that this location is within.
-  pub region: Resource<Region>,
+  /// The region that this location is within, if any.
+  pub region: Option<NamedResource<Region>>,
   /// Areas within this location.
   pub areas: Vec<Resource<Area>>,
 }
@@ -102,15 +122,40 @@ pub struct Encounterable {
   pub encounters: Vec<VersionedEncounters>,
 }
 
+/// An [`Area`] where a particular [`Pokemon`] can be encountered.
+///
+/// A list of these is obtained by loading
+/// [`Pokemon::location_area_encounters`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AreaEncounters {
+  /// The area the Pokemon can be encountered in.
+  #[serde(rename = "location_area")]
+  pub area: Resource<Area>,
+  /// Encounters with the Pokemon in this area, by version.
+  #[serde(rename = "version_details")]
+  pub versions: Vec<VersionedEncounters>,
+}
+
+impl AreaEncounters {
+  /// Returns the encounters in this area in `version`, if there are any.
+  pub fn in_version(
+    &self,
+    version: VersionName,
+  ) -> Option<&VersionedEncounters> {
+    self.versions.iter().find(|v| v.version.is(version))
+  }
+}
+
 /// [`Encounter`]s with a particular [`Pokemon`] in a certain [`Version`].
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct VersionedEncounters {
   /// The version this encounter is relevant for.
-  pub version: Resource<Version>,
+  pub version: NamedResource<Version>,
   /// The percentage of the total encounter potential this encounter represents.
   pub max_chance: Percent,
   /// Ways this encounter can play out, i.e., what combination of method
   /// and condition result in what levels and rate observed?
+  #[serde(rename = "encounter_details")]
   pub encounters: Vec<Encounter>,
 }
 
@@ -147,7 +192,7 @@ pub struct EncounterMethodRateVersion {
   /// The chance for this encounter method to succeed.
   pub rate: Percent,
   /// The version this rate is valid for.
-  pub versions: Resource<Version>,
+  pub version: NamedResource<Version>,
 }
 
 impl Endpoint for Area {
@@ -202,7 +247,7 @@ pub struct EncounterConditionValue {
   #[serde(rename = "names")]
   pub localized_names: Localized,
   /// The condition this value is for.
-  pub condition: Vec<Resource<EncounterCondition>>,
+  pub condition: Resource<EncounterCondition>,
 }
 
 impl Endpoint for EncounterConditionValue {
@@ -234,5 +279,9 @@ pub struct PalParkEncounter {
   pub rate: Percent,
   /// The species being encountered.
   #[serde(rename = "pokemon_species")]
-  pub species: Vec<Resource<Species>>,
+  pub species: Resource<Species>,
+}
+
+impl Endpoint for PalParkArea {
+  const NAME: &'static str = "pal-park-area";
 }
@@ -10,6 +10,7 @@ pub use resource::Resource;
 pub mod text;
 pub use text::Language;
 pub use text::LanguageName;
+pub use text::LanguagePreference;
 
 pub mod ability;
 pub mod berry;
@@ -44,6 +45,17 @@ pub use stat::Stat;
 pub use ty::Type;
 pub use version::Generation;
 
+pub use ability::AbilityName;
+pub use item::ItemName;
+pub use location::RegionName;
+pub use mov::DamageClassName;
+pub use mov::TargetName;
+pub use nature::NatureName;
 pub use pokedex::PokedexName;
+pub use species::EggGroupName;
+pub use species::GrowthRateName;
 pub use stat::StatName;
 pub use ty::TypeName;
+pub use version::GenerationName;
+pub use version::VersionGroupName;
+pub use version::VersionName;
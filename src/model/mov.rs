@@ -6,7 +6,10 @@ use serde::Serialize;
 
 use crate::api::Endpoint;
 use crate::model::contest;
+use crate::model::item;
 use crate::model::item::Tm;
+use crate::model::item::TmVersion;
+use crate::model::resource::NameOf;
 use crate::model::resource::NamedResource;
 use crate::model::resource::Resource;
 use crate::model::text;
@@ -15,6 +18,7 @@ use crate::model::text::Localized;
 use crate::model::ty::Type;
 use crate::model::version::Generation;
 use crate::model::version::VersionGroup;
+use crate::model::version::VersionGroupName;
 use crate::model::Percent;
 
 text_field!(flavor_text, description: Desc);
@@ -31,18 +35,19 @@ pub struct Move {
   pub localized_names: Localized,
 
   /// The generation this move was introduced in.
-  pub generation: Resource<Generation>,
-  /// TMs that can teach this move.
+  pub generation: NamedResource<Generation>,
+  /// TMs that can teach this move in various versions.
   #[serde(rename = "machines")]
-  pub tms: Vec<Resource<Tm>>,
+  pub tms: Vec<TmVersion>,
 
   /// This move's accuracy, i.e, it's base chance to connect with an opposing
-  /// Pokemon.
-  pub accuracy: u32,
-  /// This move's base power, which is used to base damage calculations.
-  pub power: u32,
+  /// Pokemon; missing for moves that never miss.
+  pub accuracy: Option<u32>,
+  /// This move's base power, which is used to base damage calculations;
+  /// missing for moves that do not deal damage directly.
+  pub power: Option<u32>,
   /// This move's base power points, the number of times it can be used.
-  pub pp: u32,
+  pub pp: Option<u32>,
   /// This move's priority, indicating the order in which it occurs releative to
   /// other moves, ignoring speed.
   ///
@@ -51,17 +56,17 @@ pub struct Move {
   pub priority: i8,
   /// This move's damage class, specifying whether it uses physical or
   /// special stats (or neither).
-  pub damage_class: Resource<DamageClass>,
+  pub damage_class: NamedResource<DamageClass>,
   /// This move's target on the field.
-  pub target: Resource<Target>,
+  pub target: NamedResource<Target>,
   /// This move's given type.
   #[serde(rename = "type")]
   pub ty: NamedResource<Type>,
 
-  /// The chance this move's secondary effect will occur.
-  pub effect_chance: Percent,
-  /// Metadata for this move.
-  pub meta: Meta,
+  /// The chance this move's secondary effect will occur, if it has one.
+  pub effect_chance: Option<Percent>,
+  /// Metadata for this move, if any.
+  pub meta: Option<Meta>,
 
   /// Effect text for this move in various languages.
   #[serde(rename = "effect_entries")]
@@ -85,6 +90,17 @@ pub struct Move {
   pub super_contest_effect: Option<Resource<contest::SuperEffect>>,
 }
 
+impl Move {
+  /// Returns the TM that teaches this move in `version_group`, if there is
+  /// one.
+  pub fn tm_in(
+    &self,
+    version_group: VersionGroupName,
+  ) -> Option<&Resource<Tm>> {
+    item::find_tm(&self.tms, version_group)
+  }
+}
+
 /// Metadata for a particular [`Move`].
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Meta {
@@ -158,26 +174,25 @@ impl From<i8> for DrainEffect {
 /// An erratum for information about a [`Move`].
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Erratum {
-  /// This move's accuracy, i.e, it's base chance to connect with an opposing
-  /// Pokemon.
-  pub accuracy: u32,
-  /// This move's base power, which is used to base damage calculations.
-  pub power: u32,
-  /// This move's base power points, the number of times it can be used.
-  pub pp: u32,
-  /// This move's given type.
+  /// This move's accuracy, if it changed.
+  pub accuracy: Option<u32>,
+  /// This move's base power, if it changed.
+  pub power: Option<u32>,
+  /// This move's base power points, if they changed.
+  pub pp: Option<u32>,
+  /// This move's given type, if it changed.
   #[serde(rename = "type")]
-  pub ty: NamedResource<Type>,
+  pub ty: Option<NamedResource<Type>>,
 
-  /// The chance this move's secondary effect will occur.
-  pub effect_chance: Percent,
+  /// The chance this move's secondary effect will occur, if it changed.
+  pub effect_chance: Option<Percent>,
 
   /// Effect text for this move in various languages.
   #[serde(rename = "effect_entries")]
   pub effect_text: Vec<Effect>,
 
   /// The version group this erratum applies to.
-  pub version_group: Resource<VersionGroup>,
+  pub version_group: NamedResource<VersionGroup>,
 }
 
 impl Endpoint for Move {
@@ -227,7 +242,7 @@ pub struct DamageClass {
   /// This class's numeric ID.
   pub id: u32,
   /// This class's API name.
-  pub name: String,
+  pub name: NameOf<Self>,
   /// The name of this class in various languages.
   #[serde(rename = "names")]
   pub localized_names: Localized,
@@ -242,13 +257,25 @@ impl Endpoint for DamageClass {
   const NAME: &'static str = "move-damage-class";
 }
 
+well_known! {
+  /// A name for a [`DamageClass`].
+  pub enum DamageClassName for DamageClass {
+    /// Moves that do not deal damage directly.
+    Status => "status",
+    /// Moves that use Attack and Defense.
+    Physical => "physical",
+    /// Moves that use Special Attack and Special Defense.
+    Special => "special",
+  }
+}
+
 /// A [`Move`] target, describing what is affected by it in battle.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Target {
   /// This target's numeric ID.
   pub id: u32,
   /// This target's API name.
-  pub name: String,
+  pub name: NameOf<Self>,
   /// The name of this target in various languages.
   #[serde(rename = "names")]
   pub localized_names: Localized,
@@ -262,3 +289,26 @@ pub struct Target {
 impl Endpoint for Target {
   const NAME: &'static str = "move-target";
 }
+
+well_known! {
+  /// A name for a [`Target`].
+  #[allow(missing_docs)]
+  pub enum TargetName for Target {
+    SpecificMove => "specific-move",
+    SelectedPokemonMeFirst => "selected-pokemon-me-first",
+    Ally => "ally",
+    UsersField => "users-field",
+    UserOrAlly => "user-or-ally",
+    OpponentsField => "opponents-field",
+    User => "user",
+    RandomOpponent => "random-opponent",
+    AllOtherPokemon => "all-other-pokemon",
+    SelectedPokemon => "selected-pokemon",
+    AllOpponents => "all-opponents",
+    EntireField => "entire-field",
+    UserAndAllies => "user-and-allies",
+    AllPokemon => "all-pokemon",
+    AllAllies => "all-allies",
+    FaintingPokemon => "fainting-pokemon",
+  }
+}
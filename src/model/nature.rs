@@ -5,10 +5,12 @@ use serde::Serialize;
 
 use crate::api::Endpoint;
 use crate::model::berry::Flavor;
+use crate::model::resource::NameOf;
 use crate::model::resource::NamedResource;
 use crate::model::resource::Resource;
 use crate::model::stat::pokeathlon;
 use crate::model::stat::Stat;
+use crate::model::stat::StatName;
 use crate::model::text::Localized;
 use crate::model::Percent;
 
@@ -20,7 +22,7 @@ pub struct Nature {
   /// This nature's numeric ID.
   pub id: u32,
   /// This nature's API name.
-  pub name: String,
+  pub name: NameOf<Self>,
   /// The name of this nature in various languages.
   #[serde(rename = "names")]
   pub localized_names: Localized,
@@ -47,6 +49,26 @@ pub struct Nature {
   pub battle_palace_preferences: Vec<BattlePalacePreference>,
 }
 
+impl Nature {
+  /// Returns whether this nature causes `stat` to grow better.
+  ///
+  /// Natures that raise and lower the same stat affect neither.
+  pub fn raises(&self, stat: StatName) -> bool {
+    is(&self.increases, stat) && !is(&self.decreases, stat)
+  }
+
+  /// Returns whether this nature causes `stat` to grow worse.
+  ///
+  /// Natures that raise and lower the same stat affect neither.
+  pub fn lowers(&self, stat: StatName) -> bool {
+    is(&self.decreases, stat) && !is(&self.increases, stat)
+  }
+}
+
+fn is(link: &Option<NamedResource<Stat>>, stat: StatName) -> bool {
+  link.as_ref().map(|s| s.is(stat)).unwrap_or(false)
+}
+
 /// How a particular nature can affect the growth of a Pokeathalon stat.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PokeathlonStatEffect {
@@ -75,6 +97,38 @@ impl Endpoint for Nature {
   const NAME: &'static str = "nature";
 }
 
+well_known! {
+  /// A name for a [`Nature`].
+  #[allow(missing_docs)]
+  pub enum NatureName for Nature {
+    Hardy => "hardy",
+    Lonely => "lonely",
+    Brave => "brave",
+    Adamant => "adamant",
+    Naughty => "naughty",
+    Bold => "bold",
+    Docile => "docile",
+    Relaxed => "relaxed",
+    Impish => "impish",
+    Lax => "lax",
+    Timid => "timid",
+    Hasty => "hasty",
+    Serious => "serious",
+    Jolly => "jolly",
+    Naive => "naive",
+    Modest => "modest",
+    Mild => "mild",
+    Quiet => "quiet",
+    Bashful => "bashful",
+    Rash => "rash",
+    Calm => "calm",
+    Gentle => "gentle",
+    Sassy => "sassy",
+    Careful => "careful",
+    Quirky => "quirky",
+  }
+}
+
 /// A battle style used for determining move choice in Emerald's Battle Palace.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BattlePlaceStyle {
@@ -98,11 +152,8 @@ impl Endpoint for BattlePlaceStyle {
 /// with a `gene_modulo` equal to that remainder.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Characteristic {
-  /// This style's numeric ID.
+  /// This characteristic's numeric ID.
   pub id: u32,
-  /// The name of this style in various languages.
-  #[serde(rename = "names")]
-  pub localized_names: Localized,
   /// Descriptions of this characteristic in various languages.
   pub descriptions: Localized<Desc>,
 
@@ -6,6 +6,7 @@ use serde::Serialize;
 use crate::api::Endpoint;
 use crate::model::location::Region;
 use crate::model::resource::NameOf;
+use crate::model::resource::NamedResource;
 use crate::model::resource::Resource;
 use crate::model::species::Species;
 use crate::model::text::Localized;
@@ -25,9 +26,9 @@ pub struct Pokedex {
   /// Whether this Pokedex is actually used in main-series games.
   pub is_main_series: bool,
   /// The region this Pokedex indexes Pokemon for.
-  pub region: Option<Resource<Region>>,
+  pub region: Option<NamedResource<Region>>,
   /// Version groups associated with this Pokedex.
-  pub version_groups: Vec<Resource<VersionGroup>>,
+  pub version_groups: Vec<NamedResource<VersionGroup>>,
 
   /// This Pokedex's entries.
   #[serde(rename = "pokemon_entries")]
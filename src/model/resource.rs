@@ -12,6 +12,8 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::api::Api;
+#[cfg(feature = "async")]
+use crate::api::AsyncApi;
 use crate::api::Endpoint;
 use crate::api::Error;
 use crate::api::Lazy;
@@ -47,6 +49,13 @@ impl<T: Endpoint> Resource<T> {
   pub fn load(&self, api: &Api) -> Result<Arc<T>, Error> {
     self.object.load(api)
   }
+
+  /// Performs an asynchronous network request to obtain the `T` represented by
+  /// this [`Resource`].
+  #[cfg(feature = "async")]
+  pub async fn load_async(&self, api: &AsyncApi) -> Result<Arc<T>, Error> {
+    self.object.load_async(api).await
+  }
 }
 
 /// A named PokeAPI resource.
@@ -89,6 +98,13 @@ impl<T: Endpoint + Named> NamedResource<T> {
   pub fn load(&self, api: &Api) -> Result<Arc<T>, Error> {
     self.object.load(api)
   }
+
+  /// Performs an asynchronous network request to obtain the `T` represented by
+  /// this [`NamedResource`].
+  #[cfg(feature = "async")]
+  pub async fn load_async(&self, api: &AsyncApi) -> Result<Arc<T>, Error> {
+    self.object.load_async(api).await
+  }
 }
 
 impl<T: Named> From<Resource<T>> for NamedResource<T> {
@@ -8,13 +8,18 @@ use serde::Serialize;
 
 use crate::api::Blob;
 use crate::api::Endpoint;
+use crate::api::Lazy;
 use crate::model::ability::Ability;
 use crate::model::evolution::Family;
 use crate::model::item::HeldRarity;
 use crate::model::item::Item;
+use crate::model::location::AreaEncounters;
 use crate::model::location::PalParkArea;
+use crate::model::location::RegionName;
 use crate::model::mov::Move;
 use crate::model::pokedex::Pokedex;
+use crate::model::resource::Name;
+use crate::model::resource::NameOf;
 use crate::model::resource::NamedResource;
 use crate::model::resource::Resource;
 use crate::model::stat::Stat;
@@ -79,14 +84,13 @@ pub struct Pokemon {
   /// Types this Pokemon has.
   pub types: Vec<ValidType>,
   /// Items this Pokemon can be found holding in the wild.
-  #[serde(default)]
+  #[serde(rename = "held_items")]
   pub items: Vec<HeldItem>,
   /// Base stat values for this Pokemon.
   pub stats: Vec<BaseStat>,
 
-  /// ???
-  // TODO
-  pub location_area_encounters: String,
+  /// The areas where this Pokemon can be encountered in the wild.
+  pub location_area_encounters: Lazy<Vec<AreaEncounters>>,
 }
 
 /// A Pokemon's height.
@@ -140,7 +144,7 @@ pub struct ValidAbility {
   /// Which ability slot this ability belongs to.
   pub slot: u8,
   /// The corresponding ability.
-  pub ability: Resource<Ability>,
+  pub ability: NamedResource<Ability>,
 }
 
 /// A [`Move`] a particular [`Pokemon`] can have.
@@ -164,7 +168,7 @@ pub struct ValidMoveSource {
   #[serde(rename = "move_learn_method")]
   pub method: Resource<LearnMethod>,
   /// The version group this source is valid for.
-  pub version_group: Resource<VersionGroup>,
+  pub version_group: NamedResource<VersionGroup>,
 }
 
 /// An [`Item`] that a particular [`Pokemon`] can be holding in the wold.
@@ -174,7 +178,7 @@ pub struct HeldItem {
   #[serde(rename = "version_details")]
   pub rarities: Vec<HeldRarity>,
   /// The corresponding item.
-  pub item: Resource<Item>,
+  pub item: NamedResource<Item>,
 }
 
 /// A [`Type`] a particular [`Pokemon`] has.
@@ -237,6 +241,42 @@ pub struct SpriteSet {
   pub back_shiny_female: Option<Blob>,
 }
 
+impl Pokemon {
+  /// Returns the part of this Pokemon's API name that distinguishes it from
+  /// the default variety of its species, if any.
+  ///
+  /// For example, this returns `"alola"` for "raichu-alola" and `"mega-x"` for
+  /// "charizard-mega-x".
+  pub fn form_name(&self) -> Option<&str> {
+    if self.is_default {
+      return None;
+    }
+    let species = self.species.name()?;
+    self.name.strip_prefix(species)?.strip_prefix('-')
+  }
+
+  /// Returns what kind of variety this Pokemon is, such as a regional form or
+  /// a Mega Evolution.
+  pub fn form_kind(&self) -> FormKind {
+    match self.form_name() {
+      Some(form_name) => FormKind::from_form_name(form_name),
+      None if self.is_default => FormKind::Default,
+      None => FormKind::Other,
+    }
+  }
+
+  /// Returns whether this Pokemon is a regional form, such as Alolan Raichu.
+  pub fn is_regional(&self) -> bool {
+    matches!(self.form_kind(), FormKind::Regional(_))
+  }
+
+  /// Returns whether this Pokemon is a Mega Evolution, such as Mega Charizard
+  /// X.
+  pub fn is_mega(&self) -> bool {
+    self.form_kind() == FormKind::Mega
+  }
+}
+
 impl Endpoint for Pokemon {
   const NAME: &'static str = "pokemon";
 }
@@ -247,7 +287,98 @@ impl Endpoint for Pokemon {
 /// apparence.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Form {
-  // TODO
+  /// This form's numeric ID.
+  pub id: u32,
+  /// This form's API name.
+  pub name: String,
+  /// The name of this form, as distinct from the name of its [`Pokemon`].
+  ///
+  /// For example, the form "raichu-alola" has the form name "alola". This is
+  /// empty for most default forms.
+  pub form_name: String,
+  /// This form's ordering number among all forms.
+  pub order: u32,
+  /// This form's ordering number among the forms of its species.
+  pub form_order: u32,
+
+  /// Whether this is the default form for its [`Pokemon`].
+  pub is_default: bool,
+  /// Whether this form is only available during battle.
+  pub is_battle_only: bool,
+  /// Whether this form is a Mega Evolution.
+  pub is_mega: bool,
+
+  /// The Pokemon this form belongs to.
+  pub pokemon: Resource<Pokemon>,
+  /// Types this form has, if they differ from its Pokemon's.
+  pub types: Vec<ValidType>,
+  /// This form's battle sprites.
+  pub sprites: SpriteSet,
+  /// The version group this form was introduced in.
+  pub version_group: NamedResource<VersionGroup>,
+
+  /// The full name of this form in various languages, such as
+  /// "Alolan Raichu".
+  #[serde(rename = "names")]
+  pub localized_names: Localized,
+  /// The form name of this form in various languages, such as "Alola Form".
+  #[serde(rename = "form_names")]
+  pub localized_form_names: Localized,
+}
+
+impl Form {
+  /// Returns what kind of form this is.
+  pub fn kind(&self) -> FormKind {
+    if self.is_mega {
+      return FormKind::Mega;
+    }
+    FormKind::from_form_name(&self.form_name)
+  }
+}
+
+/// A kind of [`Form`] or [`Pokemon`] variety, as returned by [`Form::kind()`]
+/// and [`Pokemon::form_kind()`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FormKind {
+  /// A default form, such as "Kanto" Raichu.
+  Default,
+  /// A regional form, such as Alolan Raichu.
+  Regional(RegionName),
+  /// A Mega Evolution or Primal Reversion, such as Mega Charizard X.
+  Mega,
+  /// A Gigantamax form, such as Gigantamax Pikachu.
+  Gigantamax,
+  /// Any other alternate form, such as Giratina's Origin Forme.
+  Other,
+}
+
+impl FormKind {
+  /// Regions with their own regional forms.
+  const REGIONS: &'static [RegionName] = &[
+    RegionName::Alola,
+    RegionName::Galar,
+    RegionName::Hisui,
+    RegionName::Paldea,
+  ];
+
+  /// Guesses a form's kind from its form name, such as `"alola"` or
+  /// `"mega-x"`.
+  fn from_form_name(form_name: &str) -> Self {
+    if form_name.is_empty() {
+      return Self::Default;
+    }
+    let first = form_name.split('-').next().unwrap_or_default();
+    // Pikachu's caps are named after regions, but are not regional forms.
+    let region = Self::REGIONS.iter().find(|r| r.to_str() == first);
+    if let Some(&region) = region.filter(|_| !form_name.ends_with("-cap")) {
+      return Self::Regional(region);
+    }
+    match first {
+      "mega" | "primal" => Self::Mega,
+      "gmax" => Self::Gigantamax,
+      _ => Self::Other,
+    }
+  }
 }
 
 impl Endpoint for Form {
@@ -267,7 +398,7 @@ pub struct LearnMethod {
   /// Descriptions of this method in various languages.
   pub descriptions: Localized<Desc>,
   /// The version groups that this method is present in.
-  pub version_group: Vec<Resource<VersionGroup>>,
+  pub version_groups: Vec<NamedResource<VersionGroup>>,
 }
 
 impl Endpoint for LearnMethod {
@@ -286,7 +417,7 @@ pub struct Species {
   pub localized_names: Localized,
 
   /// The generation this species was introduced in.
-  pub generation: Resource<Generation>,
+  pub generation: NamedResource<Generation>,
   /// This species' ordering number. This can be used to sort species by
   /// National Pokedex number, except that evolution families are grouped
   /// together and sorted by stage.
@@ -305,14 +436,14 @@ pub struct Species {
   /// before including other factors.
   pub hatch_counter: u32,
   /// Egg groups this species belongs to.
-  pub egg_groups: Vec<Resource<EggGroup>>,
+  pub egg_groups: Vec<NamedResource<EggGroup>>,
 
   /// This species' capture rate.
   pub capture_rate: u8,
   /// This species' base happiness value when first captured.
   pub base_happiness: u8,
   /// The rate at which this species gains levels.
-  pub growth_rate: Resource<GrowthRate>,
+  pub growth_rate: NamedResource<GrowthRate>,
 
   /// Whether this species is a baby Pokemon.
   pub is_baby: bool,
@@ -423,6 +554,35 @@ impl TryFrom<i8> for GenderRatio {
   }
 }
 
+/// A gender, which can affect breeding, evolution, and appearance.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Gender {
+  /// This gender's numeric ID.
+  pub id: u32,
+  /// This gender's API name.
+  pub name: String,
+
+  /// Species that can have this gender, and how likely they are to have it.
+  #[serde(rename = "pokemon_species_details")]
+  pub species: Vec<GenderRate>,
+  /// Species that must have this gender in order to evolve.
+  pub required_for_evolution: Vec<Resource<Species>>,
+}
+
+/// The chance of a particular [`Species`] having a particular [`Gender`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GenderRate {
+  /// This species' gender ratio.
+  pub rate: GenderRatio,
+  /// The species in question.
+  #[serde(rename = "pokemon_species")]
+  pub species: Resource<Species>,
+}
+
+impl Endpoint for Gender {
+  const NAME: &'static str = "gender";
+}
+
 /// An entry in a Pokedex for a particular species.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DexEntry {
@@ -463,7 +623,7 @@ pub struct GrowthRate {
   /// This growth rate's numeric ID.
   pub id: u32,
   /// This growth rate's API name.
-  pub name: String,
+  pub name: NameOf<Self>,
   /// Descriptions of this growth rate in different languages.
   pub descriptions: Localized<Desc>,
 
@@ -471,8 +631,7 @@ pub struct GrowthRate {
   ///
   /// This string is LaTeX-formatted.
   pub formula: String,
-  /// The amount of experience needed to get to a particular level from the
-  /// previous level.
+  /// The total amount of experience needed to reach each level.
   pub levels: Vec<GrowthRateLevel>,
   /// Species that have this growth rate.
   #[serde(rename = "pokemon_species")]
@@ -493,13 +652,28 @@ impl Endpoint for GrowthRate {
   const NAME: &'static str = "growth-rate";
 }
 
+well_known! {
+  /// A name for a [`GrowthRate`].
+  #[allow(missing_docs)]
+  pub enum GrowthRateName for GrowthRate {
+    Slow => "slow",
+    Medium => "medium",
+    Fast => "fast",
+    MediumSlow => "medium-slow",
+    /// The "Erratic" growth rate.
+    Erratic => "slow-then-very-fast",
+    /// The "Fluctuating" growth rate.
+    Fluctuating => "fast-then-very-slow",
+  }
+}
+
 /// An egg group, which two species must share in order to breed.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EggGroup {
   /// This egg group's numeric ID.
   pub id: u32,
   /// This egg group's API name.
-  pub name: String,
+  pub name: NameOf<Self>,
   /// The name of this egg group in various languages.
   #[serde(rename = "names")]
   pub localized_names: Localized,
@@ -513,6 +687,29 @@ impl Endpoint for EggGroup {
   const NAME: &'static str = "egg-group";
 }
 
+well_known! {
+  /// A name for an [`EggGroup`].
+  #[allow(missing_docs)]
+  pub enum EggGroupName for EggGroup {
+    Monster => "monster",
+    Water1 => "water1",
+    Bug => "bug",
+    Flying => "flying",
+    Field => "ground",
+    Fairy => "fairy",
+    Grass => "plant",
+    HumanLike => "humanshape",
+    Water3 => "water3",
+    Mineral => "mineral",
+    Amorphous => "indeterminate",
+    Water2 => "water2",
+    Ditto => "ditto",
+    Dragon => "dragon",
+    /// Species that cannot breed.
+    Undiscovered => "no-eggs",
+  }
+}
+
 /// A color, which can be used for sorting through a Pokedex.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Color {
@@ -10,6 +10,7 @@ use crate::model::mov::Move;
 use crate::model::nature::Characteristic;
 use crate::model::nature::Nature;
 use crate::model::resource::NameOf;
+use crate::model::resource::NamedResource;
 use crate::model::resource::Resource;
 use crate::model::text::Localized;
 
@@ -33,16 +34,16 @@ pub struct Stat {
 
   /// The damage class relevant to this stat, if any.
   #[serde(rename = "move_damage_class")]
-  pub damage_class: Option<Resource<DamageClass>>,
+  pub damage_class: Option<NamedResource<DamageClass>>,
 
   /// Charactesristics which a Pokemon can have when this is its highest stat.
   pub characteristics: Vec<Resource<Characteristic>>,
   /// Natures which can affect how this stat grows.
   #[serde(rename = "affecting_natures")]
-  pub natures: Option<NatureEffects>,
+  pub natures: NatureEffects,
   /// Moves which can affect this stat in battle.
   #[serde(rename = "affecting_moves")]
-  pub moves: Vec<MoveEffects>,
+  pub moves: MoveEffects,
 }
 
 well_known! {
@@ -75,10 +76,10 @@ well_known! {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NatureEffects {
   /// Natures that make this stat grow better.
-  pub increase: Vec<Resource<Nature>>,
+  pub increase: Vec<NamedResource<Nature>>,
 
   /// Natures that make this stat grow worse.
-  pub decrease: Vec<Resource<Nature>>,
+  pub decrease: Vec<NamedResource<Nature>>,
 }
 
 /// Moves which affect the value of a stat in battle.
@@ -94,8 +95,10 @@ pub struct MoveEffects {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MoveEffect {
   /// The maximum delta for this move effect.
+  #[serde(rename = "change")]
   pub delta: i32,
   /// The move causing this stat change.
+  #[serde(rename = "move")]
   pub mov: Resource<Move>,
 }
 
@@ -108,7 +111,7 @@ pub mod pokeathlon {
 
   use super::*;
 
-  /// A Pokeathalon statistic
+  /// A Pokeathlon statistic.
   #[derive(Clone, Debug, Serialize, Deserialize)]
   pub struct Stat {
     /// This stat's numeric ID.
@@ -121,7 +124,7 @@ pub mod pokeathlon {
 
     /// Natures which can affect how this stat grows.
     #[serde(rename = "affecting_natures")]
-    pub natures: Vec<NatureEffects>,
+    pub natures: NatureEffects,
   }
 
   /// Natures which affect the growth of a particular stat.
@@ -137,12 +140,13 @@ pub mod pokeathlon {
   #[derive(Clone, Debug, Serialize, Deserialize)]
   pub struct NatureEffect {
     /// The maximum delta for this nature effect.
+    #[serde(rename = "max_change")]
     pub delta: i32,
-    /// The move causing this stat change.
-    pub nature: Resource<Nature>,
+    /// The nature causing this stat change.
+    pub nature: NamedResource<Nature>,
   }
 
   impl Endpoint for Stat {
-    const NAME: &'static str = "pokeathalon-stat";
+    const NAME: &'static str = "pokeathlon-stat";
   }
 }
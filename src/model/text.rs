@@ -12,11 +12,14 @@ use serde::Serialize;
 
 use crate::api::Endpoint;
 use crate::model::resource::NameOf;
+use crate::model::resource::Named;
 use crate::model::resource::NamedResource;
-use crate::model::resource::Resource;
 use crate::model::version::Version;
 use crate::model::version::VersionGroup;
 
+#[cfg(doc)]
+use crate::model::resource::Resource;
+
 /// A language that text can be localized for.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Language {
@@ -32,8 +35,8 @@ pub struct Language {
   pub official: bool,
   /// The two-letter ISO 636 code for this language's country; not unique.
   pub iso639: Option<String>,
-  /// The two-letter ISO 3155 code for this language; not unique.
-  pub iso3155: Option<String>,
+  /// The two-letter ISO 3166 code for this language; not unique.
+  pub iso3166: Option<String>,
 }
 
 impl Endpoint for Language {
@@ -81,7 +84,7 @@ well_known! {
 /// The type of `Field` is an implementation detail for providing the
 /// serialization name of `text`, while `Version` may either be `()` to
 /// indicate no version, or one of [`Version`] or [`VersionGroup`], in which
-/// case `version will have the type [`Resource<V>`].
+/// case `version` will have the type [`NamedResource<V>`].
 #[derive(Clone, Debug)]
 pub struct Text<Field, Version: VersionField = ()> {
   /// The localized text.
@@ -105,7 +108,7 @@ pub struct Text<Field, Version: VersionField = ()> {
 /// The type of `Field` is an implementation detail for providing the
 /// serialization name of `text`, while `Version` may either be `()` to
 /// indicate no version, or one of [`Version`] or [`VersionGroup`], in which
-/// case `version will have the type [`Resource<V>`].
+/// case `version` will have the type [`NamedResource<V>`].
 #[rustfmt::skip]
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(transparent)]
@@ -121,16 +124,152 @@ impl<F, V: VersionField> Localized<F, V> {
   pub fn iter(&self) -> impl Iterator<Item = &Text<F, V>> + '_ {
     self.names.iter()
   }
+
+  /// Returns the localization for the most preferred language in `langs` that
+  /// is present.
+  ///
+  /// If this text is versioned, the first localization listed for that
+  /// language is returned, regardless of version.
+  pub fn get(&self, langs: impl AsRef<[LanguageName]>) -> Option<&str> {
+    self.find(langs, |_| true)
+  }
+
+  /// Returns the localization for the most preferred language in `langs` among
+  /// those which `filter` accepts.
+  fn find(
+    &self,
+    langs: impl AsRef<[LanguageName]>,
+    filter: impl Fn(&Text<F, V>) -> bool,
+  ) -> Option<&str> {
+    langs.as_ref().iter().find_map(|&lang| {
+      self
+        .names
+        .iter()
+        .find(|text| text.language.is(lang) && filter(text))
+        .map(|text| &text.text[..])
+    })
+  }
+}
+
+impl<F, V> Localized<F, V>
+where
+  V: VersionField<TYPE = NamedResource<V>> + Named,
+{
+  /// Returns the localization for `version`, in the most preferred language in
+  /// `langs` that is present.
+  pub fn get_in(
+    &self,
+    version: V::Variant,
+    langs: impl AsRef<[LanguageName]>,
+  ) -> Option<&str> {
+    self.find(langs, |text| text.version.is(version))
+  }
+
+  /// Like [`Localized::get_in()`], but falls back to the latest version if
+  /// there is no localization for `version`.
+  ///
+  /// Versions are ordered by ID, which PokeAPI assigns in release order.
+  pub fn get_in_or_latest(
+    &self,
+    version: V::Variant,
+    langs: impl AsRef<[LanguageName]>,
+  ) -> Option<&str> {
+    let langs = langs.as_ref();
+    self.get_in(version, langs).or_else(|| {
+      langs.iter().find_map(|&lang| {
+        self
+          .names
+          .iter()
+          .filter(|text| text.language.is(lang))
+          .max_by_key(|text| id_in(text.version.url()))
+          .map(|text| &text.text[..])
+      })
+    })
+  }
+}
+
+/// Returns the numeric ID at the end of a resource URL, if there is one.
+fn id_in(url: &str) -> Option<u32> {
+  url.trim_end_matches('/').rsplit('/').next()?.parse().ok()
+}
+
+/// An ordered list of languages to look up [`Localized`] text in.
+///
+/// The first language that a particular piece of text is available in is used.
+/// A single [`LanguageName`] can be used anywhere a [`LanguagePreference`] is
+/// expected, as can an array or slice of them. Lookups borrow their languages,
+/// so a preference that is kept around can be passed by reference:
+///
+/// ```
+/// # use pkmn::model::*;
+/// let prefs = LanguagePreference::from([LanguageName::German])
+///   .then(LanguageName::English);
+/// assert_eq!(prefs, [LanguageName::German, LanguageName::English].into());
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LanguagePreference {
+  langs: Vec<LanguageName>,
 }
 
-impl<F> Localized<F> {
-  /// Returns the localization for a particular language, if one is present.
+impl LanguagePreference {
+  /// Adds `lang` to the end of this chain, as the least-preferred language.
   #[inline]
-  pub fn get(&self, lang: LanguageName) -> Option<&str> {
+  pub fn then(mut self, lang: LanguageName) -> Self {
+    self.langs.push(lang);
     self
-      .iter()
-      .find(|text| text.language.is(lang))
-      .map(|text| &text.text[..])
+  }
+
+  /// Returns an iterator over the languages in this chain, from most to least
+  /// preferred.
+  #[inline]
+  pub fn iter(&self) -> impl Iterator<Item = LanguageName> + '_ {
+    self.langs.iter().copied()
+  }
+}
+
+impl Default for LanguagePreference {
+  fn default() -> Self {
+    LanguageName::English.into()
+  }
+}
+
+impl From<LanguageName> for LanguagePreference {
+  fn from(lang: LanguageName) -> Self {
+    Self { langs: vec![lang] }
+  }
+}
+
+impl<const N: usize> From<[LanguageName; N]> for LanguagePreference {
+  fn from(langs: [LanguageName; N]) -> Self {
+    Self {
+      langs: langs.to_vec(),
+    }
+  }
+}
+
+impl From<&[LanguageName]> for LanguagePreference {
+  fn from(langs: &[LanguageName]) -> Self {
+    Self {
+      langs: langs.to_vec(),
+    }
+  }
+}
+
+impl From<Vec<LanguageName>> for LanguagePreference {
+  fn from(langs: Vec<LanguageName>) -> Self {
+    Self { langs }
+  }
+}
+
+impl AsRef<[LanguageName]> for LanguagePreference {
+  fn as_ref(&self) -> &[LanguageName] {
+    &self.langs
+  }
+}
+
+impl AsRef<[LanguageName]> for LanguageName {
+  fn as_ref(&self) -> &[LanguageName] {
+    std::slice::from_ref(self)
   }
 }
 
@@ -159,7 +298,7 @@ pub struct Erratum {
   #[serde(rename = "effect_entries")]
   pub localized_errata: Vec<Effect>,
   /// The version for this particular erratum.
-  pub version_group: Resource<VersionGroup>,
+  pub version_group: NamedResource<VersionGroup>,
 }
 
 #[doc(hidden)]
@@ -193,12 +332,12 @@ pub trait VersionField: Sized {
 }
 
 impl VersionField for Version {
-  type TYPE = Resource<Self>;
+  type TYPE = NamedResource<Self>;
   const NAME: Option<&'static str> = Some("version");
 }
 
 impl VersionField for VersionGroup {
-  type TYPE = Resource<Self>;
+  type TYPE = NamedResource<Self>;
   const NAME: Option<&'static str> = Some("version_group");
 }
 
@@ -398,7 +537,7 @@ impl<'de, F: TextField, V: VersionField> Deserialize<'de> for Text<F, V> {
   }
 }
 
-impl<'de, F: TextField, V: VersionField> Serialize for Text<F, V> {
+impl<F: TextField, V: VersionField> Serialize for Text<F, V> {
   fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
     use serde::ser::SerializeStruct;
 
@@ -12,6 +12,8 @@ use crate::model::resource::Resource;
 use crate::model::species::Pokemon;
 use crate::model::text::Localized;
 use crate::model::version::Generation;
+use crate::model::version::GenerationGameId;
+use crate::model::version::GenerationName;
 
 /// A Pokemon type.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -25,15 +27,15 @@ pub struct Type {
   pub localized_names: Localized,
 
   /// The generation this type was introduced in.
-  pub generation: Resource<Generation>,
-  /*/// The internal game ids for this type.
+  pub generation: NamedResource<Generation>,
+  /// The internal game ids for this type.
   #[serde(rename = "game_indices")]
-  pub game_ids: Vec<GameId>,*/
+  pub game_ids: Vec<GenerationGameId>,
   /// The damage class this type inflicted prior to Generation IV.
   ///
   /// Some types, like Fairy, have no damage class.
   #[serde(rename = "move_damage_class")]
-  pub damage_class: Option<Resource<DamageClass>>,
+  pub damage_class: Option<NamedResource<DamageClass>>,
 
   /// Pokemon which have this type.
   #[serde(rename = "pokemon")]
@@ -41,6 +43,34 @@ pub struct Type {
   /// How this type relates to other types.
   #[serde(rename = "damage_relations")]
   pub relations: Relations,
+  /// How this type related to other types in past generations, if that was
+  /// different.
+  #[serde(rename = "past_damage_relations")]
+  pub past_relations: Vec<PastRelations>,
+}
+
+impl Type {
+  /// Returns how this type related to other types in `generation`.
+  pub fn relations_in(&self, generation: GenerationName) -> &Relations {
+    // Past relations apply up to and including their generation, so the
+    // earliest one that is not before `generation` is the one that applies.
+    self
+      .past_relations
+      .iter()
+      .filter_map(|past| Some((past.generation.variant()?, &past.relations)))
+      .filter(|&(g, _)| g >= generation)
+      .min_by_key(|&(g, _)| g)
+      .map(|(_, relations)| relations)
+      .unwrap_or(&self.relations)
+  }
+}
+
+// Lets slices of `Type`s, and not just of `Arc<Type>`s, be used to build a
+// `TypeChart`.
+impl AsRef<Type> for Type {
+  fn as_ref(&self) -> &Type {
+    self
+  }
 }
 
 well_known! {
@@ -106,6 +136,17 @@ pub struct Relations {
   pub weak_to: Vec<NamedResource<Type>>,
 }
 
+/// How a particular type was related to other types, up to and including a
+/// particular generation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PastRelations {
+  /// The last generation these relations were in effect for.
+  pub generation: NamedResource<Generation>,
+  /// How the type related to other types.
+  #[serde(rename = "damage_relations")]
+  pub relations: Relations,
+}
+
 impl Endpoint for Type {
   const NAME: &'static str = "type";
 }
@@ -8,6 +8,7 @@ use crate::model::ability::Ability;
 use crate::model::location::Region;
 use crate::model::mov::Move;
 use crate::model::pokedex::Pokedex;
+use crate::model::resource::NameOf;
 use crate::model::resource::NamedResource;
 use crate::model::resource::Resource;
 use crate::model::species::Species;
@@ -20,18 +21,18 @@ pub struct Generation {
   /// This generation's numeric ID.
   pub id: u32,
   /// This generation's API name.
-  pub name: String,
+  pub name: NameOf<Self>,
   /// The name of this generation in various languages.
   #[serde(rename = "names")]
   pub localized_names: Localized,
 
   /// The maion region introduced in this generation.
-  pub main_region: Resource<Region>,
+  pub main_region: NamedResource<Region>,
   /// Version groups associated with this generation.
-  pub version_groups: Vec<Resource<VersionGroup>>,
+  pub version_groups: Vec<NamedResource<VersionGroup>>,
 
   /// Abilities introduced in this generation.
-  pub abilities: Vec<Resource<Ability>>,
+  pub abilities: Vec<NamedResource<Ability>>,
   /// Moves introduced in this generation.
   pub moves: Vec<Resource<Move>>,
   /// Species introduced in this generation.
@@ -51,13 +52,54 @@ pub struct GameId {
   #[serde(rename = "game_index")]
   pub id: u32,
   /// The version this index is applicable for.
-  pub version: Resource<Version>,
+  pub version: NamedResource<Version>,
+}
+
+/// An internal id value for an entity in a particular generation of games.
+///
+/// This is like a [`GameId`], but for entities whose ids only change between
+/// generations, such as [`Type`]s.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GenerationGameId {
+  /// The internal id value for this entity.
+  #[serde(rename = "game_index")]
+  pub id: u32,
+  /// The generation this index is applicable for.
+  pub generation: NamedResource<Generation>,
 }
 
 impl Endpoint for Generation {
   const NAME: &'static str = "generation";
 }
 
+well_known! {
+  /// A name for a [`Generation`].
+  ///
+  /// Generations are ordered chronologically.
+  #[derive(PartialOrd, Ord)]
+  pub enum GenerationName for Generation {
+    /// Generation I: Red, Blue, and Yellow.
+    I => "generation-i",
+    /// Generation II: Gold, Silver, and Crystal.
+    II => "generation-ii",
+    /// Generation III: Ruby, Sapphire, Emerald, FireRed, and LeafGreen.
+    III => "generation-iii",
+    /// Generation IV: Diamond, Pearl, Platinum, HeartGold, and SoulSilver.
+    IV => "generation-iv",
+    /// Generation V: Black, White, Black 2, and White 2.
+    V => "generation-v",
+    /// Generation VI: X, Y, Omega Ruby, and Alpha Sapphire.
+    VI => "generation-vi",
+    /// Generation VII: Sun, Moon, Ultra Sun, Ultra Moon, and Let's Go.
+    VII => "generation-vii",
+    /// Generation VIII: Sword, Shield, Brilliant Diamond, Shining Pearl, and
+    /// Legends: Arceus.
+    VIII => "generation-viii",
+    /// Generation IX: Scarlet and Violet.
+    IX => "generation-ix",
+  }
+}
+
 /// A group of versions that are very similar, such as Ruby and Sapphire, or
 /// X and Y.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -65,37 +107,121 @@ pub struct VersionGroup {
   /// This version group's numeric ID.
   pub id: u32,
   /// This version group's API name.
-  pub name: String,
+  pub name: NameOf<Self>,
   /// Order of game groups by release date (roughly).
   pub order: u32,
   /// The generation this version was released in.
-  pub generation: Resource<Generation>,
+  pub generation: NamedResource<Generation>,
   /// The regions that can be visited in this version.
-  pub regions: Vec<Resource<Region>>,
+  pub regions: Vec<NamedResource<Region>>,
   /// The Pokedexes available in this version group.
   pub pokedexes: Vec<NamedResource<Pokedex>>,
   /// The versions that make up this group.
-  pub versions: Vec<Resource<Version>>,
+  pub versions: Vec<NamedResource<Version>>,
 }
 
 impl Endpoint for VersionGroup {
   const NAME: &'static str = "version-group";
 }
 
+well_known! {
+  /// A name for a [`VersionGroup`].
+  #[allow(missing_docs)]
+  pub enum VersionGroupName for VersionGroup {
+    RedBlue => "red-blue",
+    Yellow => "yellow",
+    GoldSilver => "gold-silver",
+    Crystal => "crystal",
+    RubySapphire => "ruby-sapphire",
+    Emerald => "emerald",
+    FireRedLeafGreen => "firered-leafgreen",
+    Colosseum => "colosseum",
+    Xd => "xd",
+    DiamondPearl => "diamond-pearl",
+    Platinum => "platinum",
+    HeartGoldSoulSilver => "heartgold-soulsilver",
+    BlackWhite => "black-white",
+    Black2White2 => "black-2-white-2",
+    XY => "x-y",
+    OmegaRubyAlphaSapphire => "omega-ruby-alpha-sapphire",
+    SunMoon => "sun-moon",
+    UltraSunUltraMoon => "ultra-sun-ultra-moon",
+    LetsGo => "lets-go-pikachu-lets-go-eevee",
+    SwordShield => "sword-shield",
+    IsleOfArmor => "the-isle-of-armor",
+    CrownTundra => "the-crown-tundra",
+    BrilliantDiamondShiningPearl => "brilliant-diamond-and-shining-pearl",
+    LegendsArceus => "legends-arceus",
+    ScarletViolet => "scarlet-violet",
+    TealMask => "the-teal-mask",
+    IndigoDisk => "the-indigo-disk",
+  }
+}
+
 /// A Pokemon game version, such as Red, Diamond, or LeafGreen.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Version {
   /// This version's numeric ID.
   pub id: u32,
   /// This version's API name.
-  pub name: String,
+  pub name: NameOf<Self>,
   /// The name of this version in various languages.
   #[serde(rename = "names")]
   pub localized_names: Localized,
   /// Which version group this version is part of.
-  pub version_group: Resource<VersionGroup>,
+  pub version_group: NamedResource<VersionGroup>,
 }
 
 impl Endpoint for Version {
   const NAME: &'static str = "version";
 }
+
+well_known! {
+  /// A name for a [`Version`].
+  #[allow(missing_docs)]
+  pub enum VersionName for Version {
+    Red => "red",
+    Blue => "blue",
+    Yellow => "yellow",
+    Gold => "gold",
+    Silver => "silver",
+    Crystal => "crystal",
+    Ruby => "ruby",
+    Sapphire => "sapphire",
+    Emerald => "emerald",
+    FireRed => "firered",
+    LeafGreen => "leafgreen",
+    Colosseum => "colosseum",
+    Xd => "xd",
+    Diamond => "diamond",
+    Pearl => "pearl",
+    Platinum => "platinum",
+    HeartGold => "heartgold",
+    SoulSilver => "soulsilver",
+    Black => "black",
+    White => "white",
+    Black2 => "black-2",
+    White2 => "white-2",
+    X => "x",
+    Y => "y",
+    OmegaRuby => "omega-ruby",
+    AlphaSapphire => "alpha-sapphire",
+    Sun => "sun",
+    Moon => "moon",
+    UltraSun => "ultra-sun",
+    UltraMoon => "ultra-moon",
+    LetsGoPikachu => "lets-go-pikachu",
+    LetsGoEevee => "lets-go-eevee",
+    Sword => "sword",
+    Shield => "shield",
+    IsleOfArmor => "the-isle-of-armor",
+    CrownTundra => "the-crown-tundra",
+    BrilliantDiamond => "brilliant-diamond",
+    ShiningPearl => "shining-pearl",
+    LegendsArceus => "legends-arceus",
+    Scarlet => "scarlet",
+    Violet => "violet",
+    TealMask => "the-teal-mask",
+    IndigoDisk => "the-indigo-disk",
+  }
+}
@@ -0,0 +1,120 @@
+//! Tests for looking up resources with an `Api`.
+
+mod common;
+
+use std::sync::Arc;
+
+use pkmn::api::Cache;
+use pkmn::api::ErrorKind;
+use pkmn::model::Language;
+use pkmn::model::Nature;
+
+#[test]
+fn by_name_and_by_id_share_an_entry() {
+  let (api, requests) = common::logged_api(Cache::no_disk(8));
+  let by_name = api.by_name::<Language>("fr").unwrap();
+  let by_id = api.by_id::<Language>(5).unwrap();
+  assert!(Arc::ptr_eq(&by_name, &by_id));
+  assert_eq!(
+    common::take(&requests),
+    ["https://pokeapi.co/api/v2/language/fr/"]
+  );
+
+  let keys = api.cache().entries().map(|e| e.key).collect::<Vec<_>>();
+  assert_eq!(keys, ["https://pokeapi.co/api/v2/language/5/"]);
+
+  let again = api.by_name::<Language>("fr").unwrap();
+  assert!(Arc::ptr_eq(&by_name, &again));
+  assert!(common::take(&requests).is_empty());
+}
+
+#[test]
+fn by_name_finds_entries_fetched_by_id() {
+  let (api, requests) = common::logged_api(Cache::no_disk(8));
+  let by_id = api.by_id::<Language>(5).unwrap();
+  let by_name = api.by_name::<Language>("fr").unwrap();
+  assert!(Arc::ptr_eq(&by_name, &by_id));
+  assert_eq!(
+    common::take(&requests),
+    ["https://pokeapi.co/api/v2/language/5/"]
+  );
+
+  // The same goes for resources loaded through a reference to them.
+  let fr = &by_id.localized_names.iter().next().unwrap().language;
+  let (api, requests) = common::logged_api(Cache::no_disk(8));
+  fr.load(&api).unwrap();
+  api.by_name::<Language>("fr").unwrap();
+  assert_eq!(common::take(&requests).len(), 1);
+}
+
+#[test]
+fn by_name_finds_entries_on_disk() {
+  let dir = common::TempDir::new("by-name-finds-entries-on-disk");
+  let (api, _) =
+    common::logged_api(Cache::with_dir(8, dir.path().to_path_buf()));
+  api.by_name::<Language>("fr").unwrap();
+
+  let (api, requests) =
+    common::logged_api(Cache::with_dir(8, dir.path().to_path_buf()));
+  let fr = api.by_name::<Language>("fr").unwrap();
+  assert_eq!(fr.id, 5);
+  assert!(common::take(&requests).is_empty());
+}
+
+#[test]
+fn aliases_are_evicted_with_their_entries() {
+  let (api, requests) = common::logged_api(Cache::no_disk(2));
+  for name in ["fr", "de", "es"] {
+    api.by_name::<Language>(name).unwrap();
+  }
+  assert_eq!(common::take(&requests).len(), 3);
+
+  // `de` and `es` are still in memory, along with their aliases...
+  api.by_name::<Language>("es").unwrap();
+  api.by_name::<Language>("de").unwrap();
+  assert!(common::take(&requests).is_empty());
+
+  // ...but `fr` is not, and neither is its alias.
+  api.by_name::<Language>("fr").unwrap();
+  assert_eq!(
+    common::take(&requests),
+    ["https://pokeapi.co/api/v2/language/fr/"]
+  );
+}
+
+#[test]
+fn by_url_checks_the_endpoint() {
+  let (api, requests) = common::logged_api(Cache::no_disk(8));
+  let fr = api
+    .by_url::<Language>("https://pokeapi.co/api/v2/language/fr/")
+    .unwrap();
+  assert_eq!(fr.id, 5);
+  let fr = api
+    .by_url::<Language>("https://pokeapi.co/api/v2/language/5")
+    .unwrap();
+  assert_eq!(fr.id, 5);
+
+  for url in [
+    "https://pokeapi.co/api/v2/nature/2/",
+    "https://pokeapi.co/api/v2/language/",
+    "https://pokeapi.co/api/v2/language/5/names/",
+    "https://example.com/api/v2/language/5/",
+  ] {
+    let err = api.by_url::<Language>(url).unwrap_err();
+    assert!(
+      matches!(err.kind, ErrorKind::WrongEndpoint("language")),
+      "{}: {:?}",
+      url,
+      err
+    );
+    assert_eq!(err.url, url);
+  }
+  assert_eq!(
+    api
+      .by_url::<Nature>("https://pokeapi.co/api/v2/nature/2/")
+      .unwrap()
+      .id,
+    2
+  );
+  assert_eq!(common::take(&requests).len(), 2);
+}
@@ -0,0 +1,581 @@
+//! Tests for `Cache`.
+
+mod common;
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::Barrier;
+use std::thread;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use pkmn::api::transport::Conditional;
+use pkmn::api::transport::Freshness;
+use pkmn::api::Cache;
+use pkmn::api::ErrorKind;
+use pkmn::api::Options;
+use pkmn::api::TierStats;
+use pkmn::model::Language;
+use pkmn::Api;
+
+/// Returns the fixture for the `fr` language.
+fn fr() -> Vec<u8> {
+  let path = common::fixtures().join("api/v2/language/5/index.json");
+  std::fs::read(path).unwrap()
+}
+
+/// Returns an [`Api`] over the fixture tree, which uses `cache`.
+fn api(cache: Cache) -> Api {
+  Api::with_options(Options {
+    cache: Arc::new(cache),
+    ..common::options()
+  })
+}
+
+/// Returns an [`Api`] over the fixture tree, with a disk cache at `dir`
+/// and no memory cache.
+fn api_over(dir: &Path) -> Api {
+  api(Cache::with_dir(0, dir.to_path_buf()))
+}
+
+/// Returns the URL of the language with the given ID.
+fn language(id: u32) -> String {
+  format!("https://pokeapi.co/api/v2/language/{}/", id)
+}
+
+/// Returns the size of every entry in `cache`, by ID.
+fn sizes(cache: &Cache) -> BTreeMap<u32, u64> {
+  cache
+    .entries()
+    .map(|e| {
+      let id = e.key.trim_end_matches('/').rsplit('/').next().unwrap();
+      (id.parse().unwrap(), e.size)
+    })
+    .collect()
+}
+
+/// Returns the modification time of the only file in `dir`.
+fn modified(dir: &Path) -> SystemTime {
+  let mut files = std::fs::read_dir(dir).unwrap();
+  let file = files.next().unwrap().unwrap();
+  assert!(files.next().is_none());
+  file.metadata().unwrap().modified().unwrap()
+}
+
+/// Returns caching information for a response that is stale immediately.
+fn stale(etag: &str) -> Freshness {
+  Freshness {
+    max_age: Some(Duration::ZERO),
+    etag: Some(etag.to_string()),
+    last_modified: None,
+  }
+}
+
+#[test]
+fn stale_entries_are_revalidated() {
+  let (fake, requests) = common::Fake::new(|n, _| match n {
+    0 => Ok(Conditional::Modified(fr(), stale("\"v1\""))),
+    _ => Ok(Conditional::NotModified(Freshness {
+      max_age: Some(Duration::from_secs(3600)),
+      ..Freshness::default()
+    })),
+  });
+  let api = common::api_with(fake, 1);
+
+  let first = api.by_id::<Language>(5).unwrap();
+  let second = api.by_id::<Language>(5).unwrap();
+  assert!(Arc::ptr_eq(&first, &second));
+
+  let requests = requests.lock().unwrap();
+  assert_eq!(requests.len(), 2);
+  assert_eq!(requests[0].1, None);
+  assert_eq!(requests[1].1, Some(stale("\"v1\"")));
+  drop(requests);
+
+  // The 304 refreshed the entry, so it is now fresh for an hour.
+  let third = api.by_id::<Language>(5).unwrap();
+  assert!(Arc::ptr_eq(&first, &third));
+}
+
+#[test]
+fn stale_entries_are_used_if_revalidation_fails() {
+  let (fake, requests) = common::Fake::new(|n, _| match n {
+    0 => Ok(Conditional::Modified(fr(), stale("\"v1\""))),
+    _ => Err(ErrorKind::ServerError(503)),
+  });
+  let api = common::api_with(fake, 1);
+
+  let first = api.by_id::<Language>(5).unwrap();
+  let second = api.by_id::<Language>(5).unwrap();
+  assert!(Arc::ptr_eq(&first, &second));
+  assert_eq!(requests.lock().unwrap().len(), 2);
+
+  // The failure is remembered, so the server isn't asked again right away.
+  let third = api.by_id::<Language>(5).unwrap();
+  assert!(Arc::ptr_eq(&first, &third));
+  assert_eq!(requests.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn stale_entries_do_not_mask_permanent_errors() {
+  let (fake, requests) = common::Fake::new(|n, _| match n {
+    0 => Ok(Conditional::Modified(fr(), stale("\"v1\""))),
+    _ => Err(ErrorKind::NotFound(404)),
+  });
+  let api = common::api_with(fake, 1);
+
+  api.by_id::<Language>(5).unwrap();
+  assert!(api.by_id::<Language>(5).unwrap_err().is_not_found());
+  assert!(api.by_id::<Language>(5).unwrap_err().is_not_found());
+  assert_eq!(requests.lock().unwrap().len(), 3);
+}
+
+#[test]
+fn memory_stats() {
+  let api = api(Cache::no_disk(2));
+  for id in [1, 2, 3, 3] {
+    api.by_id::<Language>(id).unwrap();
+  }
+
+  let stats = api.cache().stats();
+  let bytes = sizes(api.cache()).values().sum();
+  assert_eq!(
+    stats.memory,
+    TierStats {
+      hits: 1,
+      misses: 3,
+      evictions: 1,
+      entries: 2,
+      bytes,
+    }
+  );
+  assert_eq!(stats.disk, TierStats::default());
+  assert_eq!(sizes(api.cache()).keys().collect::<Vec<_>>(), [&2, &3]);
+}
+
+#[test]
+fn disk_budget_evicts_least_recently_used() {
+  // Find out how big each entry is on disk.
+  let dir = common::TempDir::new("disk-budget-sizes");
+  let probe = api_over(dir.path());
+  for id in 1..=4 {
+    probe.by_id::<Language>(id).unwrap();
+  }
+  let size = sizes(probe.cache());
+  let kept = size[&1] + size[&3] + size[&4];
+  let budget = kept.max(size[&1] + size[&2] + size[&3]);
+
+  let dir = common::TempDir::new("disk-budget");
+  let api =
+    api(Cache::with_dir(0, dir.path().to_path_buf()).disk_budget(budget));
+  for id in 1..=3 {
+    api.by_id::<Language>(id).unwrap();
+  }
+  // Reading 1 makes 2 the least recently used entry, so it is evicted to make
+  // room for 4.
+  api.by_id::<Language>(1).unwrap();
+  api.by_id::<Language>(4).unwrap();
+
+  assert_eq!(sizes(api.cache()).keys().collect::<Vec<_>>(), [&1, &3, &4]);
+  assert!(api.cache().entries().all(|e| e.on_disk && !e.in_memory));
+  assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 3);
+
+  let stats = api.cache().stats();
+  assert_eq!(
+    stats.disk,
+    TierStats {
+      hits: 1,
+      misses: 4,
+      evictions: 1,
+      entries: 3,
+      bytes: kept,
+    }
+  );
+  assert_eq!(stats.memory.misses, 5);
+  assert_eq!(stats.memory.entries, 0);
+
+  // Going over budget again evicts 3 first, since 1 was read after it.
+  api.by_id::<Language>(5).unwrap();
+  let left = sizes(api.cache());
+  assert!(left.contains_key(&5));
+  assert!(!left.contains_key(&3));
+  assert!(left.values().sum::<u64>() <= budget);
+}
+
+#[test]
+fn disk_hits_do_not_write() {
+  let dir = common::TempDir::new("disk-hits-do-not-write");
+  let api = api_over(dir.path());
+  api.by_id::<Language>(5).unwrap();
+
+  let path = std::fs::read_dir(dir.path())
+    .unwrap()
+    .next()
+    .unwrap()
+    .unwrap()
+    .path();
+  let epoch = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+  File::options()
+    .write(true)
+    .open(&path)
+    .unwrap()
+    .set_modified(epoch)
+    .unwrap();
+
+  api.by_id::<Language>(5).unwrap();
+  assert_eq!(api.cache().stats().disk.hits, 1);
+  assert_eq!(modified(dir.path()), epoch);
+  assert_eq!(api.cache().entries().next().unwrap().key, language(5));
+}
+
+/// Writes an entry to a disk cache in a fresh directory, passes its contents
+/// through `corrupt`, and checks that a new cache over the same directory
+/// treats it as missing.
+fn assert_corrupt_is_miss(name: &str, corrupt: impl FnOnce(&mut Vec<u8>)) {
+  let dir = common::TempDir::new(name);
+  let api = api_over(dir.path());
+  api.by_id::<Language>(5).unwrap();
+
+  let path = std::fs::read_dir(dir.path())
+    .unwrap()
+    .next()
+    .unwrap()
+    .unwrap()
+    .path();
+  let mut buf = std::fs::read(&path).unwrap();
+  corrupt(&mut buf);
+  std::fs::write(&path, buf).unwrap();
+
+  let (fake, requests) = common::Fake::new(|_, _| {
+    Ok(Conditional::Modified(fr(), Freshness::default()))
+  });
+  let api = Api::with_options(Options {
+    cache: Arc::new(Cache::with_dir(0, dir.path().to_path_buf())),
+    transport: Box::new(fake),
+    ..common::options()
+  });
+  let fr = api.by_id::<Language>(5).unwrap();
+  assert_eq!(fr.name.to_string(), "fr");
+  assert_eq!(requests.lock().unwrap().len(), 1, "{}", name);
+
+  let stats = api.cache().stats().disk;
+  assert_eq!((stats.hits, stats.misses), (0, 1), "{}", name);
+
+  // The bad entry was replaced with a good one.
+  let api = api_over(dir.path());
+  api.by_id::<Language>(5).unwrap();
+  assert_eq!(api.cache().stats().disk.hits, 1, "{}", name);
+}
+
+/// Returns the index of the end of the header in a disk cache entry.
+fn header_end(buf: &[u8]) -> usize {
+  let magic = buf.iter().position(|&b| b == b'\n').unwrap();
+  magic + 1 + buf[magic + 1..].iter().position(|&b| b == b'\n').unwrap()
+}
+
+#[test]
+fn bad_magic_is_a_miss() {
+  assert_corrupt_is_miss("bad-magic", |buf| buf[0] ^= 0xff);
+}
+
+#[test]
+fn version_mismatch_is_a_miss() {
+  assert_corrupt_is_miss("version-mismatch", |buf| {
+    let end = header_end(buf);
+    let header = String::from_utf8(buf[..end].to_vec()).unwrap();
+    let version = format!("\"pkmn_version\":\"{}\"", env!("CARGO_PKG_VERSION"));
+    assert!(header.contains(&version));
+    let header = header.replace(&version, "\"pkmn_version\":\"0.0.0-old\"");
+    buf.splice(..end, header.into_bytes());
+  });
+}
+
+#[test]
+fn checksum_mismatch_is_a_miss() {
+  assert_corrupt_is_miss("checksum-mismatch", |buf| {
+    let last = buf.len() - 2;
+    buf[last] ^= 0x01;
+  });
+}
+
+#[test]
+fn truncated_entry_is_a_miss() {
+  assert_corrupt_is_miss("truncated-entry", |buf| {
+    buf.truncate(buf.len() - 10);
+  });
+  assert_corrupt_is_miss("truncated-header", |buf| {
+    buf.truncate(header_end(buf) - 10);
+  });
+}
+
+/// The number of threads to look up the same entry with at once.
+const THREADS: usize = 8;
+
+/// Looks up the `fr` language from [`THREADS`] threads at once, through
+/// `api`, returning each thread's result, or `None` if it panicked.
+fn race(api: &Api) -> Vec<Option<Result<Arc<Language>, pkmn::api::Error>>> {
+  let barrier = Barrier::new(THREADS);
+  thread::scope(|s| {
+    let threads = (0..THREADS)
+      .map(|_| {
+        s.spawn(|| {
+          barrier.wait();
+          api.by_id::<Language>(5)
+        })
+      })
+      .collect::<Vec<_>>();
+    threads.into_iter().map(|t| t.join().ok()).collect()
+  })
+}
+
+/// Returns a response function for a [`common::Fake`] that is slow enough for
+/// every thread in [`race()`] to pile up behind the first request.
+fn slow(
+  first: fn() -> Result<Conditional, ErrorKind>,
+) -> impl Fn(usize, Option<&Freshness>) -> Result<Conditional, ErrorKind> {
+  move |n, _| {
+    thread::sleep(Duration::from_millis(200));
+    match n {
+      0 => first(),
+      _ => Ok(Conditional::Modified(fr(), Freshness::default())),
+    }
+  }
+}
+
+#[test]
+fn concurrent_lookups_share_one_request() {
+  let (fake, requests) = common::Fake::new(slow(|| {
+    Ok(Conditional::Modified(fr(), Freshness::default()))
+  }));
+  let api = common::api_with(fake, 1);
+
+  let results = race(&api)
+    .into_iter()
+    .map(|r| r.unwrap().unwrap())
+    .collect::<Vec<_>>();
+  assert_eq!(requests.lock().unwrap().len(), 1);
+  assert!(results.iter().all(|r| Arc::ptr_eq(r, &results[0])));
+}
+
+#[test]
+fn waiters_retry_after_failed_lookup() {
+  let (fake, requests) =
+    common::Fake::new(slow(|| Err(ErrorKind::NotFound(404))));
+  let api = common::api_with(fake, 1);
+
+  let results = race(&api)
+    .into_iter()
+    .map(|r| r.unwrap())
+    .collect::<Vec<_>>();
+  // Only the first lookup failed; everyone waiting on it tried again, and
+  // then shared the second lookup's result.
+  assert_eq!(requests.lock().unwrap().len(), 2);
+  let (ok, err): (Vec<_>, Vec<_>) =
+    results.into_iter().partition(|r| r.is_ok());
+  assert_eq!(err.len(), 1);
+  assert!(err[0].as_ref().unwrap_err().is_not_found());
+  let ok = ok.into_iter().map(Result::unwrap).collect::<Vec<_>>();
+  assert!(ok.iter().all(|r| Arc::ptr_eq(r, &ok[0])));
+}
+
+#[test]
+fn waiters_retry_after_panicked_lookup() {
+  let (fake, requests) =
+    common::Fake::new(slow(|| panic!("lookup panicked on purpose")));
+  let api = common::api_with(fake, 1);
+
+  let results = race(&api);
+  assert_eq!(requests.lock().unwrap().len(), 2);
+  assert_eq!(results.iter().filter(|r| r.is_none()).count(), 1);
+  let ok = results
+    .into_iter()
+    .flatten()
+    .map(Result::unwrap)
+    .collect::<Vec<_>>();
+  assert_eq!(ok.len(), THREADS - 1);
+  assert!(ok.iter().all(|r| Arc::ptr_eq(r, &ok[0])));
+}
+
+/// Returns the IDs of the entries in `cache`, in order, along with which tiers
+/// they are in.
+fn tiers(cache: &Cache) -> Vec<(u32, bool, bool)> {
+  cache
+    .entries()
+    .map(|e| {
+      let id = e.key.trim_end_matches('/').rsplit('/').next().unwrap();
+      (id.parse().unwrap(), e.in_memory, e.on_disk)
+    })
+    .collect()
+}
+
+#[test]
+fn entries_cover_both_tiers() {
+  let dir = common::TempDir::new("entries-cover-both-tiers");
+  let api = api(Cache::with_dir(2, dir.path().to_path_buf()));
+  for id in [1, 2, 3, 2] {
+    api.by_id::<Language>(id).unwrap();
+  }
+
+  // Memory comes first, most recently used first; 1 was evicted to disk.
+  assert_eq!(
+    tiers(api.cache()),
+    [(2, true, true), (3, true, true), (1, false, true)]
+  );
+  assert!(api
+    .cache()
+    .entries()
+    .all(|e| e.age < Duration::from_secs(60)));
+  // Entries on disk report their size there.
+  let size = api.cache().entries().map(|e| e.size).sum::<u64>();
+  assert_eq!(size, api.cache().stats().disk.bytes);
+}
+
+#[test]
+fn entries_in_memory_only() {
+  let api = api(Cache::no_disk(2));
+  api.by_id::<Language>(5).unwrap();
+  let entries = api.cache().entries().collect::<Vec<_>>();
+  assert_eq!(entries.len(), 1);
+  assert_eq!(entries[0].key, language(5));
+  assert!(entries[0].in_memory && !entries[0].on_disk);
+  // Entries only in memory report the size of their serialized form.
+  assert_eq!(entries[0].size, api.cache().stats().memory.bytes);
+}
+
+#[test]
+fn invalidate_removes_from_both_tiers() {
+  let dir = common::TempDir::new("invalidate-removes-from-both-tiers");
+  let api = api(Cache::with_dir(2, dir.path().to_path_buf()));
+  for id in 1..=3 {
+    api.by_id::<Language>(id).unwrap();
+  }
+
+  assert!(api.cache().invalidate(&language(3)));
+  assert!(api.cache().invalidate(&language(1)));
+  assert_eq!(tiers(api.cache()), [(2, true, true)]);
+  assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+  let stats = api.cache().stats();
+  assert_eq!((stats.memory.entries, stats.disk.entries), (1, 1));
+
+  assert!(!api.cache().invalidate(&language(3)));
+  assert!(!api.cache().invalidate(&language(7)));
+
+  // Invalidating a resource fetched by name also forgets the name.
+  api.by_name::<Language>("fr").unwrap();
+  assert!(api.cache().invalidate(&language(5)));
+  assert!(api
+    .cache()
+    .invalidate("https://pokeapi.co/api/v2/language/fr/"));
+  assert_eq!(tiers(api.cache()), [(2, true, true)]);
+}
+
+#[test]
+fn clear_empties_both_tiers() {
+  let dir = common::TempDir::new("clear-empties-both-tiers");
+  let (fake, requests) = common::Fake::new(|_, _| {
+    Ok(Conditional::Modified(fr(), Freshness::default()))
+  });
+  let api = Api::with_options(Options {
+    cache: Arc::new(Cache::with_dir(2, dir.path().to_path_buf())),
+    transport: Box::new(fake),
+    ..common::options()
+  });
+  api.by_id::<Language>(5).unwrap();
+
+  api.cache().clear();
+  assert_eq!(api.cache().entries().count(), 0);
+  assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+  let stats = api.cache().stats();
+  assert_eq!((stats.memory.entries, stats.memory.bytes), (0, 0));
+  assert_eq!((stats.disk.entries, stats.disk.bytes), (0, 0));
+
+  api.by_id::<Language>(5).unwrap();
+  assert_eq!(requests.lock().unwrap().len(), 2);
+}
+
+/// Returns an [`Api`] with a disk cache at `dir`, whose transport always
+/// fails, so that everything must come from the cache.
+fn offline(dir: &Path) -> (Api, common::Requests) {
+  let (fake, requests) =
+    common::Fake::new(|_, _| Err(ErrorKind::ServerError(503)));
+  let api = Api::with_options(Options {
+    cache: Arc::new(Cache::with_dir(0, dir.to_path_buf())),
+    transport: Box::new(fake),
+    ..common::options()
+  });
+  (api, requests)
+}
+
+#[test]
+fn export_import_roundtrip() {
+  let from = common::TempDir::new("roundtrip-from");
+  let api = api_over(from.path());
+  api.by_id::<Language>(1).unwrap();
+  api.by_id::<Language>(2).unwrap();
+  api.by_name::<Language>("fr").unwrap();
+
+  let bundle = common::TempDir::new("roundtrip-bundle");
+  let bundle = bundle.path().join("bundle");
+  // Three entries, plus the alias from `fr` to 5.
+  assert_eq!(api.cache().export(&bundle).unwrap(), 4);
+
+  let to = common::TempDir::new("roundtrip-to");
+  let (api, requests) = offline(to.path());
+  assert_eq!(api.cache().import(&bundle).unwrap(), 4);
+  assert_eq!(
+    api.by_id::<Language>(1).unwrap().name.to_string(),
+    "ja-Hrkt"
+  );
+  assert_eq!(
+    api.by_id::<Language>(2).unwrap().name.to_string(),
+    "roomaji"
+  );
+  assert_eq!(api.by_name::<Language>("fr").unwrap().id, 5);
+  assert!(requests.lock().unwrap().is_empty());
+
+  // Importing the same entries again doesn't replace anything.
+  assert_eq!(api.cache().import(&bundle).unwrap(), 0);
+}
+
+#[test]
+fn import_rejects_bad_bundles() {
+  let from = common::TempDir::new("bad-bundles-from");
+  let api = api_over(from.path());
+  api.by_id::<Language>(1).unwrap();
+  api.by_id::<Language>(2).unwrap();
+
+  let dir = common::TempDir::new("bad-bundles");
+  let bundle = dir.path().join("bundle");
+  assert_eq!(api.cache().export(&bundle).unwrap(), 2);
+  let buf = std::fs::read(&bundle).unwrap();
+  let truncated = dir.path().join("truncated");
+  std::fs::write(&truncated, &buf[..buf.len() - 10]).unwrap();
+  let to = common::TempDir::new("bad-bundles-to");
+  let (api, _) = offline(to.path());
+  let err = api.cache().import(&truncated).unwrap_err();
+  assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+
+  let not_a_bundle = dir.path().join("not-a-bundle");
+  std::fs::write(&not_a_bundle, b"{}\n").unwrap();
+  let err = api.cache().import(&not_a_bundle).unwrap_err();
+  assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+  // A corrupt manifest doesn't get to pick how much memory to allocate.
+  let huge = dir.path().join("huge");
+  let manifest = serde_json::json!({
+    "pkmn_version": env!("CARGO_PKG_VERSION"),
+    "entries": [{
+      "url": language(1),
+      "endpoint": "language",
+      "fetched_at": 0,
+      "len": u64::MAX,
+    }],
+  });
+  std::fs::write(&huge, format!("pkmn-cache-bundle 1\n{}\nshort", manifest))
+    .unwrap();
+  let err = api.cache().import(&huge).unwrap_err();
+  assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,244 @@
+//! Tests for `pkmn::calc::capture`, against hand-worked catch formulas.
+
+use pkmn::calc::capture::*;
+use pkmn::model::GenerationName::*;
+use pkmn::model::TypeName;
+
+/// The shake probability for a catch value of 15 in Generations III and IV.
+const SHAKE_III_15: f64 = 32274.0 / 65536.0;
+/// The shake probability for a catch value of 15 from Generation V onward.
+const SHAKE_V_15: f64 = 38527.0 / 65536.0;
+
+/// A Pokemon with a capture rate of 45, such as a starter, at full HP.
+fn starter() -> Capture {
+  Capture::new(45, 100)
+}
+
+#[test]
+fn always_catches() {
+  for generation in [I, II, IV, IX] {
+    let master = starter().ball(Ball::Master).chance(generation);
+    assert_eq!(master.per_throw, 1.0);
+    assert_eq!(master.critical, 0.0);
+  }
+  assert_eq!(starter().ball(Ball::Park).chance(IV).per_throw, 1.0);
+}
+
+#[test]
+fn generation_i() {
+  // The shake check passes if a random number is at most
+  // 100 * 255 / 12 / (100 / 4) = 85; the capture rate check if one is at
+  // most 45.
+  assert_eq!(starter().chance(I).per_throw, 46.0 * 86.0 / 65536.0);
+
+  // The Great Ball rolls out of 201 and divides by 8, for a shake value of
+  // 127.
+  let great = starter().ball(Ball::Great).chance(I);
+  assert_eq!(great.per_throw, 23.0 / 201.0);
+
+  // Balls other than the Great, Ultra and Safari Balls act like Poke Balls.
+  for ball in [Ball::Net, Ball::Dusk, Ball::Beast] {
+    assert_eq!(starter().ball(ball).chance(I), starter().chance(I));
+  }
+  let safari = starter().ball(Ball::Safari).chance(I);
+  assert_eq!(safari.per_throw, 46.0 * 86.0 / 256.0 / 151.0);
+
+  // Status conditions succeed outright for the lowest rolls.
+  let paralyzed = Capture::new(3, 100)
+    .status(Status::Paralysis)
+    .ball(Ball::Ultra)
+    .chance(I);
+  assert_eq!(paralyzed.per_throw, (12.0 + 4.0 * 86.0 / 256.0) / 151.0);
+  let asleep = Capture::new(255, 100).hp(1).status(Status::Sleep).chance(I);
+  assert_eq!(asleep.per_throw, 1.0);
+  assert_eq!(asleep.critical, 0.0);
+}
+
+#[test]
+fn generation_ii() {
+  // The catch value is 100 * 45 / 300 = 15.
+  assert_eq!(starter().chance(II).per_throw, 16.0 / 256.0);
+  assert_eq!(starter().hp(1).chance(II).per_throw, 45.0 / 256.0);
+
+  // Only sleep and freezing help, because of a bug.
+  let asleep = starter().status(Status::Sleep);
+  assert_eq!(asleep.chance(II).per_throw, 26.0 / 256.0);
+  let frozen = starter().status(Status::Freeze);
+  assert_eq!(frozen.chance(II).per_throw, 26.0 / 256.0);
+  let burned = starter().status(Status::Burn);
+  assert_eq!(burned.chance(II).per_throw, 16.0 / 256.0);
+
+  // The Great Ball's rate of 67 makes for a catch value of 22.
+  let great = starter().ball(Ball::Great);
+  assert_eq!(great.chance(II).per_throw, 23.0 / 256.0);
+
+  // The Level Ball multiplies by 8 when four times the level, capping the
+  // rate at 255.
+  let level = starter().ball(Ball::Level).level(10);
+  assert_eq!(level.own_level(40).chance(II).per_throw, 86.0 / 256.0);
+  assert_eq!(level.own_level(10).chance(II).per_throw, 16.0 / 256.0);
+}
+
+#[test]
+fn generation_iii_and_iv() {
+  for generation in [III, IV] {
+    let chance = starter().chance(generation);
+    assert_eq!(chance.per_throw, SHAKE_III_15.powi(4));
+    assert_eq!(chance.critical, 0.0);
+  }
+
+  // Critical captures do not exist yet.
+  let chance = starter().caught_species(600).chance(IV);
+  assert_eq!(chance.per_throw, SHAKE_III_15.powi(4));
+  assert_eq!(chance.critical, 0.0);
+
+  // A catch value of 255 or more always succeeds.
+  let chance = Capture::new(255, 100).hp(1).ball(Ball::Ultra).chance(IV);
+  assert_eq!(chance.per_throw, 1.0);
+}
+
+#[test]
+fn generation_v_onward() {
+  // Generation V only makes three shake checks.
+  assert_eq!(starter().chance(V).per_throw, SHAKE_V_15.powi(3));
+  for generation in [VI, VII, VIII, IX] {
+    assert_eq!(starter().chance(generation).per_throw, SHAKE_V_15.powi(4));
+  }
+}
+
+#[test]
+fn critical_captures() {
+  // With 600 species caught, the critical capture value is 15 * 2 / 6 = 5.
+  let critical = 5.0 / 256.0;
+  let chance = starter().caught_species(600).chance(V);
+  assert_eq!(chance.critical, critical);
+  assert_eq!(
+    chance.per_throw,
+    critical * SHAKE_V_15 + (1.0 - critical) * SHAKE_V_15.powi(3)
+  );
+
+  let chance = starter().caught_species(600).chance(VI);
+  assert_eq!(chance.critical, critical);
+  assert_eq!(
+    chance.per_throw,
+    critical * SHAKE_V_15 + (1.0 - critical) * SHAKE_V_15.powi(4)
+  );
+
+  // Each tier of the Pokedex raises the odds.
+  let critical = |caught| starter().caught_species(caught).chance(VI).critical;
+  assert_eq!(critical(30), 0.0);
+  assert_eq!(critical(31), 1.0 / 256.0);
+  assert_eq!(critical(151), 2.0 / 256.0);
+  assert_eq!(critical(301), 3.0 / 256.0);
+  assert_eq!(critical(451), 5.0 / 256.0);
+  assert_eq!(critical(601), 6.0 / 256.0);
+}
+
+#[test]
+fn status_bonuses() {
+  // Multiplying the catch value is the same as multiplying the capture rate,
+  // when the product is a whole number.
+  let with_rate = |rate, generation| Capture::new(rate, 100).chance(generation);
+  let with_status = |rate, status, generation| {
+    Capture::new(rate, 100).status(status).chance(generation)
+  };
+
+  assert_eq!(with_status(30, Status::Sleep, IV), with_rate(60, IV));
+  assert_eq!(with_status(30, Status::Freeze, IV), with_rate(60, IV));
+  assert_eq!(with_status(30, Status::Burn, IV), with_rate(45, IV));
+  assert_eq!(with_status(30, Status::Poison, VI), with_rate(45, VI));
+  assert_eq!(with_status(30, Status::Paralysis, VI), with_rate(45, VI));
+  assert_eq!(with_status(30, Status::Sleep, VI), with_rate(75, VI));
+}
+
+#[test]
+fn ball_bonuses() {
+  let with_rate = |rate, generation| Capture::new(rate, 100).chance(generation);
+  let with_ball = |capture: Capture, generation| capture.chance(generation);
+  let base = Capture::new(10, 100);
+
+  assert_eq!(with_ball(base.ball(Ball::Great), IV), with_rate(15, IV));
+  assert_eq!(with_ball(base.ball(Ball::Ultra), IV), with_rate(20, IV));
+
+  // The Net Ball only helps against Water and Bug types.
+  let net = base.ball(Ball::Net);
+  assert_eq!(with_ball(net, VI), with_rate(10, VI));
+  let water = net.types(&[TypeName::Water, TypeName::Ground]);
+  assert_eq!(with_ball(water, VI), with_rate(30, VI));
+  assert_eq!(with_ball(water, VII), with_rate(35, VII));
+
+  // The Dusk Ball was weakened in Generation VII.
+  let dusk = base.ball(Ball::Dusk).dark(true);
+  assert_eq!(with_ball(dusk, VI), with_rate(35, VI));
+  assert_eq!(with_ball(dusk, VII), with_rate(30, VII));
+  assert_eq!(with_ball(dusk.dark(false), VII), with_rate(10, VII));
+
+  // The Quick Ball only works on the first turn.
+  let quick = base.ball(Ball::Quick);
+  assert_eq!(with_ball(quick, IV), with_rate(40, IV));
+  assert_eq!(with_ball(quick, V), with_rate(50, V));
+  assert_eq!(with_ball(quick.turn(2), V), with_rate(10, V));
+
+  // The Timer Ball grows by 1x every ten turns in Generation IV, and by about
+  // 0.3x every turn afterward; both cap at 4x.
+  let timer = base.ball(Ball::Timer);
+  assert_eq!(with_ball(timer, IV), with_rate(10, IV));
+  assert_eq!(with_ball(timer.turn(11), IV), with_rate(20, IV));
+  assert_eq!(with_ball(timer.turn(31), IV), with_rate(40, IV));
+  assert_eq!(with_ball(timer.turn(99), IV), with_rate(40, IV));
+  assert_eq!(with_ball(timer.turn(11), V), with_rate(40, V));
+
+  // The Nest Ball favors low levels, more so from Generation V onward.
+  let nest = base.ball(Ball::Nest);
+  assert_eq!(with_ball(nest.level(10), IV), with_rate(30, IV));
+  assert_eq!(with_ball(nest.level(11), V), with_rate(30, V));
+  assert_eq!(with_ball(nest.level(50), V), with_rate(10, V));
+
+  // The Repeat, Dive and Dream Balls depend on the circumstances.
+  let repeat = base.ball(Ball::Repeat);
+  assert_eq!(with_ball(repeat, IV), with_rate(10, IV));
+  assert_eq!(with_ball(repeat.caught_before(true), IV), with_rate(30, IV));
+  let dive = base.ball(Ball::Dive).underwater(true);
+  assert_eq!(with_ball(dive, IV), with_rate(35, IV));
+  let dream = base.ball(Ball::Dream);
+  assert_eq!(with_ball(dream, VIII), with_rate(10, VIII));
+  let asleep = Capture::new(40, 100).status(Status::Sleep);
+  assert_eq!(
+    with_ball(dream.status(Status::Sleep), VIII),
+    asleep.chance(VIII)
+  );
+
+  // The Dream Ball only became useful outside the Dream World in Generation
+  // VIII.
+  let asleep = Capture::new(10, 100).status(Status::Sleep);
+  assert_eq!(
+    with_ball(dream.status(Status::Sleep), VII),
+    asleep.chance(VII)
+  );
+
+  // The Beast Ball is much better against Ultra Beasts, and much worse
+  // against everything else.
+  let beast = Capture::new(100, 100).hp(1).ball(Ball::Beast);
+  let at_low_hp =
+    |rate, generation| Capture::new(rate, 100).hp(1).chance(generation);
+  assert_eq!(with_ball(beast, VII), at_low_hp(10, VII));
+  assert_eq!(with_ball(beast, VI), at_low_hp(100, VI));
+  let ultra_beast = beast.ultra_beast(true);
+  assert_eq!(with_ball(ultra_beast, VII).per_throw, 1.0);
+  let ultra_beast = base.ball(Ball::Beast).ultra_beast(true);
+  assert_eq!(with_ball(ultra_beast, VII), with_rate(50, VII));
+
+  // Balls this module does not model act like Poke Balls.
+  assert_eq!(with_ball(base.ball(Ball::Heavy), IV), with_rate(10, IV));
+}
+
+#[test]
+fn balls_by_name() {
+  assert_eq!(Ball::from_name("great-ball"), Some(Ball::Great));
+  assert_eq!(Ball::from_name("potion"), None);
+  assert_eq!(Ball::Dusk.name(), "dusk-ball");
+  for ball in [Ball::Poke, Ball::Cherish, Ball::Dream, Ball::Beast] {
+    assert_eq!(Ball::from_name(ball.name()), Some(ball));
+    assert_eq!(Ball::from_item_name(ball.item_name()), Some(ball));
+  }
+}
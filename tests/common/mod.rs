@@ -0,0 +1,222 @@
+//! Utilities shared between tests.
+
+// Each test crate uses only some of these.
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use pkmn::api::transport::Conditional;
+use pkmn::api::transport::Dump;
+use pkmn::api::transport::Freshness;
+use pkmn::api::transport::Transport;
+use pkmn::api::Cache;
+use pkmn::api::ErrorKind;
+use pkmn::api::Options;
+use pkmn::api::Retry;
+use pkmn::Api;
+
+/// Returns the path to the `api-data` fixture tree.
+pub fn fixtures() -> PathBuf {
+  fixture("api-data")
+}
+
+/// Returns the path to the fixture `name`.
+pub fn fixture(name: &str) -> PathBuf {
+  let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+  path.extend(&["tests", "fixtures", name]);
+  path
+}
+
+/// Deserializes the fixture for the resource at `path`, such as
+/// `"api/v2/nature/2"`, after applying `patch` to its JSON.
+///
+/// The fixture tree only has a handful of resources, so this is how tests get
+/// at resources it does not have.
+pub fn fixture_with<T: DeserializeOwned>(
+  path: &str,
+  patch: impl FnOnce(&mut Value),
+) -> T {
+  let path = fixtures().join(path).join("index.json");
+  let mut value = serde_json::from_slice(&fs::read(path).unwrap()).unwrap();
+  patch(&mut value);
+  serde_json::from_value(value).unwrap()
+}
+
+/// Returns [`Options`] for serving requests out of the fixture tree, without
+/// touching the network or the disk cache.
+pub fn options() -> Options {
+  Options {
+    cache: Arc::new(Cache::no_disk(64)),
+    transport: Box::new(Dump::new(fixtures())),
+    retry: Retry::never(),
+    ..Options::default()
+  }
+}
+
+/// Returns an [`Api`] built from [`options()`].
+pub fn api() -> Api {
+  Api::with_options(options())
+}
+
+/// The requests a [`Fake`] transport has received: each URL, along with the
+/// caching information it was made conditional on.
+pub type Requests = Arc<Mutex<Vec<(String, Option<Freshness>)>>>;
+
+/// A [`Transport`] that answers the `n`th request it receives (counting from
+/// zero) by calling a function, and records every request.
+pub struct Fake<F> {
+  respond: F,
+  requests: Requests,
+}
+
+impl<F> Fake<F>
+where
+  F: Fn(usize, Option<&Freshness>) -> Result<Conditional, ErrorKind>,
+  F: Send + Sync + 'static,
+{
+  /// Creates a new [`Fake`], returning it along with its request log.
+  pub fn new(respond: F) -> (Self, Requests) {
+    let requests = Requests::default();
+    let fake = Self {
+      respond,
+      requests: Arc::clone(&requests),
+    };
+    (fake, requests)
+  }
+}
+
+impl<F> Transport for Fake<F>
+where
+  F: Fn(usize, Option<&Freshness>) -> Result<Conditional, ErrorKind>,
+  F: Send + Sync + 'static,
+{
+  fn get(&self, url: &str) -> Result<Vec<u8>, ErrorKind> {
+    match self.get_conditional(url, None)? {
+      Conditional::Modified(buf, _) => Ok(buf),
+      Conditional::NotModified(_) => Err(ErrorKind::Status(304)),
+    }
+  }
+
+  fn get_conditional(
+    &self,
+    url: &str,
+    cached: Option<&Freshness>,
+  ) -> Result<Conditional, ErrorKind> {
+    let n = {
+      let mut requests = self.requests.lock().unwrap();
+      requests.push((url.to_string(), cached.cloned()));
+      requests.len() - 1
+    };
+    (self.respond)(n, cached)
+  }
+}
+
+/// A [`Transport`] that forwards requests to another one, and records them.
+pub struct Logged<T> {
+  inner: T,
+  requests: Requests,
+}
+
+impl<T: Transport> Logged<T> {
+  /// Creates a new [`Logged`], returning it along with its request log.
+  pub fn new(inner: T) -> (Self, Requests) {
+    let requests = Requests::default();
+    let logged = Self {
+      inner,
+      requests: Arc::clone(&requests),
+    };
+    (logged, requests)
+  }
+}
+
+impl<T: Transport> Transport for Logged<T> {
+  fn get(&self, url: &str) -> Result<Vec<u8>, ErrorKind> {
+    self.requests.lock().unwrap().push((url.to_string(), None));
+    self.inner.get(url)
+  }
+
+  fn get_conditional(
+    &self,
+    url: &str,
+    cached: Option<&Freshness>,
+  ) -> Result<Conditional, ErrorKind> {
+    let request = (url.to_string(), cached.cloned());
+    self.requests.lock().unwrap().push(request);
+    self.inner.get_conditional(url, cached)
+  }
+}
+
+/// Returns an [`Api`] over the fixture tree that uses `cache`, along with its
+/// request log.
+pub fn logged_api(cache: Cache) -> (Api, Requests) {
+  let (logged, requests) = Logged::new(Dump::new(fixtures()));
+  let api = Api::with_options(Options {
+    cache: Arc::new(cache),
+    transport: Box::new(logged),
+    ..options()
+  });
+  (api, requests)
+}
+
+/// Returns the URLs requested so far, in order, and clears the log.
+pub fn take(requests: &Requests) -> Vec<String> {
+  let mut requests = requests.lock().unwrap();
+  requests.drain(..).map(|(url, _)| url).collect()
+}
+
+/// Returns a successful response with `value` as its body.
+pub fn json(value: Value, freshness: Freshness) -> Conditional {
+  Conditional::Modified(serde_json::to_vec(&value).unwrap(), freshness)
+}
+
+/// Returns an [`Api`] that makes requests with `transport`, retrying them up to
+/// `max_attempts` times without waiting in between.
+pub fn api_with(transport: impl Transport, max_attempts: u32) -> Api {
+  Api::with_options(Options {
+    transport: Box::new(transport),
+    retry: Retry {
+      max_attempts,
+      initial_backoff: Duration::ZERO,
+      ..Retry::default()
+    },
+    ..options()
+  })
+}
+
+/// A temporary directory, which is deleted when dropped.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+  /// Creates a new, empty temporary directory, unique to this process and
+  /// `name`.
+  pub fn new(name: &str) -> Self {
+    let path = std::env::temp_dir().join(format!(
+      "pkmn-test-{}-{}",
+      process::id(),
+      name
+    ));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    Self(path)
+  }
+
+  /// Returns the path to this directory.
+  pub fn path(&self) -> &Path {
+    &self.0
+  }
+}
+
+impl Drop for TempDir {
+  fn drop(&mut self) {
+    let _ = fs::remove_dir_all(&self.0);
+  }
+}
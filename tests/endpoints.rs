@@ -0,0 +1,118 @@
+//! Tests that every endpoint deserializes from a real PokéAPI payload.
+
+mod common;
+
+use pkmn::model::*;
+
+macro_rules! endpoints {
+  ($($test:ident: $ty:ty = $id:literal,)*) => {$(
+    #[test]
+    fn $test() {
+      let api = common::api();
+      let value = api.by_id::<$ty>($id).unwrap();
+      assert_eq!(value.id, $id);
+    }
+  )*}
+}
+
+endpoints! {
+  ability: Ability = 65,
+  berry: Berry = 1,
+  berry_firmness: berry::Firmness = 2,
+  berry_flavor: berry::Flavor = 1,
+  characteristic: nature::Characteristic = 1,
+  contest_effect: contest::Effect = 1,
+  contest_type: contest::Type = 1,
+  egg_group: EggGroup = 1,
+  encounter_condition: location::EncounterCondition = 1,
+  encounter_condition_value: location::EncounterConditionValue = 1,
+  encounter_method: location::EncounterMethod = 1,
+  evolution_chain: evolution::Family = 1,
+  evolution_trigger: evolution::Trigger = 1,
+  gender: species::Gender = 1,
+  generation: Generation = 1,
+  growth_rate: species::GrowthRate = 4,
+  item: Item = 305,
+  item_attribute: item::Attribute = 1,
+  item_category: item::Category = 37,
+  item_fling_effect: item::FlingEffect = 1,
+  item_pocket: item::Pocket = 4,
+  language: Language = 1,
+  location: Location = 1,
+  location_area: location::Area = 1,
+  machine: Tm = 1,
+  mov: Move = 14,
+  move_ailment: mov::Status = 1,
+  move_battle_style: nature::BattlePlaceStyle = 1,
+  move_category: mov::Category = 2,
+  move_damage_class: mov::DamageClass = 1,
+  move_learn_method: species::LearnMethod = 1,
+  move_target: mov::Target = 7,
+  nature: Nature = 2,
+  pal_park_area: location::PalParkArea = 1,
+  pokeathlon_stat: stat::pokeathlon::Stat = 1,
+  pokedex: Pokedex = 2,
+  pokemon: Pokemon = 1,
+  pokemon_color: species::Color = 5,
+  pokemon_form: species::Form = 1,
+  pokemon_habitat: species::Habitat = 3,
+  pokemon_shape: species::Shape = 8,
+  pokemon_species: Species = 1,
+  region: Region = 1,
+  stat: Stat = 2,
+  super_contest_effect: contest::SuperEffect = 1,
+  ty: Type = 12,
+  version: version::Version = 1,
+  version_group: version::VersionGroup = 1,
+}
+
+#[test]
+fn pokemon_encounters() {
+  let api = common::api();
+  let bulbasaur = api.by_id::<Pokemon>(1).unwrap();
+  let areas = bulbasaur.location_area_encounters.load(&api).unwrap();
+  assert_eq!(areas.len(), 2);
+  assert!(areas[1].in_version(VersionName::FireRed).is_some());
+}
+
+#[test]
+fn machines_by_version() {
+  let api = common::api();
+  let tm01 = api.by_id::<Item>(305).unwrap();
+  let tm = tm01
+    .tm_in(VersionGroupName::RedBlue)
+    .unwrap()
+    .load(&api)
+    .unwrap();
+  let vg = tm.version_group.load(&api).unwrap();
+  assert!(vg.name.is(VersionGroupName::RedBlue));
+}
+
+#[test]
+fn well_known_abilities_and_items() {
+  let api = common::api();
+  let overgrow = api.by_id::<Ability>(65).unwrap();
+  assert!(overgrow.name.is(AbilityName::Overgrow));
+  let bulbasaur = api.by_id::<Pokemon>(1).unwrap();
+  assert!(bulbasaur
+    .abilities
+    .iter()
+    .any(|a| a.ability.is(AbilityName::Overgrow)));
+  assert_eq!(
+    "poison-puppeteer".parse::<AbilityName>().ok(),
+    Some(AbilityName::PoisonPuppeteer)
+  );
+
+  // Most items are not well-known.
+  let tm01 = api.by_id::<Item>(305).unwrap();
+  assert_eq!(tm01.name.as_str(), "tm01");
+  assert_eq!(tm01.name.variant(), None);
+  assert_eq!(
+    "dusk-ball".parse::<ItemName>().ok(),
+    Some(ItemName::DuskBall)
+  );
+  assert_eq!(
+    "beast-ball".parse::<ItemName>().ok(),
+    Some(ItemName::BeastBall)
+  );
+}
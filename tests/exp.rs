@@ -0,0 +1,143 @@
+//! Tests for `pkmn::calc::exp`, against Bulbapedia's experience tables.
+
+mod common;
+
+use pkmn::calc::exp::*;
+use pkmn::model::species::GrowthRate;
+use pkmn::model::GenerationName;
+use pkmn::model::GrowthRateName::*;
+
+#[test]
+fn simple_growth_rates() {
+  for rate in [Fast, Medium, MediumSlow, Slow, Erratic, Fluctuating] {
+    assert_eq!(rate.experience_at(0), 0);
+    assert_eq!(rate.experience_at(1), 0);
+  }
+
+  assert_eq!(Fast.experience_at(2), 6);
+  assert_eq!(Fast.experience_at(50), 100_000);
+  assert_eq!(Fast.experience_at(100), 800_000);
+
+  assert_eq!(Medium.experience_at(2), 8);
+  assert_eq!(Medium.experience_at(50), 125_000);
+  assert_eq!(Medium.experience_at(100), 1_000_000);
+
+  // The formula dips below zero at level 1.
+  assert_eq!(MediumSlow.experience_at(2), 9);
+  assert_eq!(MediumSlow.experience_at(3), 57);
+  assert_eq!(MediumSlow.experience_at(50), 117_360);
+  assert_eq!(MediumSlow.experience_at(100), 1_059_860);
+
+  assert_eq!(Slow.experience_at(2), 10);
+  assert_eq!(Slow.experience_at(50), 156_250);
+  assert_eq!(Slow.experience_at(100), 1_250_000);
+
+  // Levels past 100 are treated as 100.
+  assert_eq!(Slow.experience_at(101), 1_250_000);
+}
+
+#[test]
+fn erratic() {
+  assert_eq!(Erratic.experience_at(2), 15);
+  assert_eq!(Erratic.experience_at(49), 120_001);
+  assert_eq!(Erratic.experience_at(50), 125_000);
+  assert_eq!(Erratic.experience_at(67), 249_633);
+  assert_eq!(Erratic.experience_at(68), 257_834);
+  assert_eq!(Erratic.experience_at(97), 571_333);
+  assert_eq!(Erratic.experience_at(98), 583_539);
+  assert_eq!(Erratic.experience_at(100), 600_000);
+}
+
+#[test]
+fn fluctuating() {
+  assert_eq!(Fluctuating.experience_at(2), 4);
+  assert_eq!(Fluctuating.experience_at(14), 1_591);
+  assert_eq!(Fluctuating.experience_at(15), 1_957);
+  assert_eq!(Fluctuating.experience_at(35), 42_017);
+  assert_eq!(Fluctuating.experience_at(36), 46_656);
+  assert_eq!(Fluctuating.experience_at(100), 1_640_000);
+}
+
+#[test]
+fn levels_from_experience() {
+  assert_eq!(Erratic.level_at(0), 1);
+  assert_eq!(Erratic.level_at(124_999), 49);
+  assert_eq!(Erratic.level_at(125_000), 50);
+  assert_eq!(Erratic.level_at(u32::MAX), 100);
+
+  assert_eq!(Medium.experience_to_next(0), Some(8));
+  assert_eq!(Medium.experience_to_next(100), Some(25));
+  assert_eq!(Medium.experience_to_next(1_000_000), None);
+  assert_eq!(Fluctuating.experience_to_next(1_957), Some(2_457 - 1_957));
+}
+
+#[test]
+fn growth_rate_table() {
+  // The fixture only lists levels 1, 2 and 100; the rest come from the
+  // formula.
+  let api = common::api();
+  let rate = api.by_id::<GrowthRate>(4).unwrap();
+  assert_eq!(rate.experience_at(2), Some(9));
+  assert_eq!(rate.experience_at(50), Some(117_360));
+  assert_eq!(rate.experience_at(100), Some(1_059_860));
+  assert_eq!(rate.level_at(117_359), Some(49));
+  assert_eq!(rate.experience_to_next(0), Some(9));
+}
+
+#[test]
+fn formulas_by_generation() {
+  let formula = Formula::for_generation;
+  assert_eq!(formula(GenerationName::I), Formula::Flat);
+  assert_eq!(formula(GenerationName::IV), Formula::Flat);
+  assert_eq!(formula(GenerationName::V), Formula::Scaled);
+  assert_eq!(formula(GenerationName::VI), Formula::Flat);
+  assert_eq!(formula(GenerationName::VII), Formula::Modern);
+  assert_eq!(formula(GenerationName::IX), Formula::Modern);
+}
+
+#[test]
+fn flat_gains() {
+  let gain = ExpGain::new(64, 50);
+  assert_eq!(gain.compute(Formula::Flat), 457);
+  assert_eq!(gain.trainer(true).compute(Formula::Flat), 685);
+  assert_eq!(gain.traded(true).compute(Formula::Flat), 685);
+  assert_eq!(gain.lucky_egg(true).compute(Formula::Flat), 685);
+  assert_eq!(gain.participants(2).compute(Formula::Flat), 228);
+  assert_eq!(gain.participants(0).compute(Formula::Flat), 457);
+
+  // The victor's level does not matter.
+  assert_eq!(gain.victor_level(5).compute(Formula::Flat), 457);
+}
+
+#[test]
+fn scaled_gains() {
+  let gain = ExpGain::new(64, 50);
+  assert_eq!(gain.compute(Formula::Scaled), 641);
+  assert_eq!(gain.trainer(true).compute(Formula::Scaled), 961);
+  assert_eq!(gain.lucky_egg(true).compute(Formula::Scaled), 961);
+  assert_eq!(
+    gain
+      .traded(true)
+      .international(true)
+      .compute(Formula::Scaled),
+    1_089
+  );
+  assert_eq!(gain.participants(2).compute(Formula::Scaled), 321);
+
+  // Weaker victors gain more, and stronger ones less.
+  assert_eq!(gain.victor_level(25).compute(Formula::Scaled), 1_220);
+  assert_eq!(gain.victor_level(75).compute(Formula::Scaled), 384);
+  assert_eq!(gain.victor_level(100).compute(Formula::Scaled), 251);
+}
+
+#[test]
+fn modern_gains() {
+  let gain = ExpGain::new(64, 50);
+  assert_eq!(gain.compute(Formula::Modern), 641);
+  assert_eq!(gain.victor_level(25).compute(Formula::Modern), 1_220);
+
+  // There is no bonus for defeating a trainer's Pokemon.
+  assert_eq!(gain.trainer(true).compute(Formula::Modern), 641);
+  assert_eq!(gain.lucky_egg(true).compute(Formula::Modern), 961);
+  assert_eq!(gain.traded(true).compute(Formula::Modern), 961);
+}
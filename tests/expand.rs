@@ -0,0 +1,154 @@
+//! Tests for loading linked resources with `Api::expand()`.
+
+mod common;
+
+use std::sync::Arc;
+
+use pkmn::api::Cache;
+use pkmn::api::Expand;
+use pkmn::model::berry::Flavor;
+use pkmn::model::text::Localized;
+use pkmn::model::Language;
+use pkmn::model::LanguageName;
+use pkmn::model::Move;
+use pkmn::model::NamedResource;
+use pkmn::model::Nature;
+use pkmn::model::Stat;
+
+/// Returns the French language links in `names`; the fixture tree does not
+/// contain the other languages they link to.
+fn french(names: &Localized) -> Vec<&NamedResource<Language>> {
+  names
+    .iter()
+    .map(|text| &text.language)
+    .filter(|lang| lang.variant() == Some(LanguageName::French))
+    .collect()
+}
+
+/// Follows a nature to the stat it lowers and the flavor it hates, and from
+/// there to the stat's natures and moves.
+fn graph() -> Expand {
+  Expand::new()
+    .links(|n: &Nature| n.decreases.iter().collect())
+    .links(|n: &Nature| n.hates_flavor.iter().collect())
+    .links(|s: &Stat| s.natures.decrease.iter().collect())
+    .links(|s: &Stat| s.moves.increase.iter().map(|e| &e.mov).collect())
+}
+
+#[test]
+fn depth_limits_how_far_links_are_followed() {
+  let (api, requests) = common::logged_api(Cache::no_disk(64));
+  let bold = api.by_id::<Nature>(2).unwrap();
+  common::take(&requests);
+
+  let shallow = api.expand(Arc::clone(&bold), &graph().depth(1)).unwrap();
+  assert_eq!(shallow.len(), 2);
+  let mut requested = common::take(&requests);
+  requested.sort();
+  assert_eq!(
+    requested,
+    [
+      "https://pokeapi.co/api/v2/berry-flavor/1/",
+      "https://pokeapi.co/api/v2/stat/2/",
+    ]
+  );
+  let attack = shallow.get(bold.decreases.as_ref().unwrap()).unwrap();
+  assert_eq!(attack.id, 2);
+  assert!(shallow.get(&attack.moves.increase[0].mov).is_none());
+
+  // The second hop comes back around to the root, which is loaded like any
+  // other node; it is already cached, so only the move is requested.
+  let deep = api.expand(Arc::clone(&bold), &graph().depth(2)).unwrap();
+  assert_eq!(deep.len(), 4);
+  assert_eq!(
+    common::take(&requests),
+    ["https://pokeapi.co/api/v2/move/14/"]
+  );
+  let swords_dance = deep.get(&attack.moves.increase[0].mov).unwrap();
+  assert_eq!(swords_dance.id, 14);
+  assert!(Arc::ptr_eq(
+    &deep.get(&attack.natures.decrease[0]).unwrap(),
+    &bold
+  ));
+
+  // Nothing links onward from a move, so a deeper search stops early.
+  let deeper = api.expand(Arc::clone(&bold), &graph().depth(5)).unwrap();
+  assert_eq!(deeper.len(), 4);
+  assert!(common::take(&requests).is_empty());
+}
+
+#[test]
+fn shared_links_are_loaded_once() {
+  let (api, requests) = common::logged_api(Cache::no_disk(64));
+  let bold = api.by_id::<Nature>(2).unwrap();
+  common::take(&requests);
+
+  let how = graph()
+    .depth(3)
+    .links(|n: &Nature| french(&n.localized_names))
+    .links(|s: &Stat| french(&s.localized_names))
+    .links(|f: &Flavor| french(&f.localized_names))
+    .links(|m: &Move| french(&m.localized_names));
+  let expanded = api.expand(bold, &how).unwrap();
+
+  // Every resource links to French, but it is only requested once.
+  let requested = common::take(&requests);
+  let french_urls = requested
+    .iter()
+    .filter(|url| url.ends_with("/language/5/"))
+    .count();
+  assert_eq!(french_urls, 1);
+  assert_eq!(expanded.len(), 5);
+
+  let fr = expanded.get(french(&expanded.localized_names)[0]).unwrap();
+  assert_eq!(fr.id, 5);
+  let attack = expanded.get(expanded.decreases.as_ref().unwrap()).unwrap();
+  let stat_fr = expanded.get(french(&attack.localized_names)[0]).unwrap();
+  assert!(Arc::ptr_eq(&fr, &stat_fr));
+}
+
+#[test]
+fn get_all_skips_unloaded_links() {
+  let (api, _) = common::logged_api(Cache::no_disk(64));
+  let bold = api.by_id::<Nature>(2).unwrap();
+  let expanded = api.expand(Arc::clone(&bold), &graph().depth(2)).unwrap();
+
+  let attack = expanded.get(bold.decreases.as_ref().unwrap()).unwrap();
+  let moves = attack
+    .moves
+    .increase
+    .iter()
+    .chain(&attack.moves.decrease)
+    .map(|e| &e.mov);
+  let loaded = expanded.get_all(moves).map(|m| m.id).collect::<Vec<_>>();
+  assert_eq!(loaded, [14]);
+
+  // Links that were never followed are absent, even if they point to
+  // resources that exist.
+  assert!(expanded.get(french(&bold.localized_names)[0]).is_none());
+  assert!(Arc::ptr_eq(expanded.root(), &bold));
+  assert_eq!(expanded.id, bold.id);
+}
+
+#[test]
+fn errors_are_propagated() {
+  let (api, _) = common::logged_api(Cache::no_disk(64));
+  let bold = api.by_id::<Nature>(2).unwrap();
+
+  // Bold raises Defense, which is missing from the fixture tree.
+  let how = Expand::new().links(|n: &Nature| n.increases.iter().collect());
+  let error = api.expand(bold, &how).err().unwrap();
+  assert!(error.is_not_found());
+  assert!(error.to_string().contains("/stat/3/"));
+}
+
+#[test]
+fn nothing_to_follow() {
+  let (api, requests) = common::logged_api(Cache::no_disk(64));
+  let bold = api.by_id::<Nature>(2).unwrap();
+  common::take(&requests);
+
+  let expanded = api.expand(bold, &Expand::new()).unwrap();
+  assert!(expanded.is_empty());
+  assert!(common::take(&requests).is_empty());
+}
@@ -0,0 +1,128 @@
+//! Tests for classifying Pokemon varieties and forms.
+
+mod common;
+
+use pkmn::model::species::Form;
+use pkmn::model::species::FormKind;
+use pkmn::model::Pokemon;
+use pkmn::model::RegionName;
+
+/// Returns a variety of `species` called `name`.
+fn pokemon(species: &str, name: &str, is_default: bool) -> Pokemon {
+  // Only Bulbasaur has a fixture, so rename it.
+  common::fixture_with("api/v2/pokemon/1", |pokemon| {
+    pokemon["name"] = name.into();
+    pokemon["is_default"] = is_default.into();
+    pokemon["species"]["name"] = species.into();
+  })
+}
+
+/// Returns a form with the form name `form_name`.
+fn form(form_name: &str, is_mega: bool) -> Form {
+  common::fixture_with("api/v2/pokemon-form/1", |form| {
+    form["form_name"] = form_name.into();
+    form["is_default"] = form_name.is_empty().into();
+    form["is_mega"] = is_mega.into();
+  })
+}
+
+#[test]
+fn default_varieties() {
+  let raichu = pokemon("raichu", "raichu", true);
+  assert_eq!(raichu.form_name(), None);
+  assert_eq!(raichu.form_kind(), FormKind::Default);
+  assert!(!raichu.is_regional());
+  assert!(!raichu.is_mega());
+
+  // Some species' default variety is itself a form.
+  let deoxys = pokemon("deoxys", "deoxys-normal", true);
+  assert_eq!(deoxys.form_name(), None);
+  assert_eq!(deoxys.form_kind(), FormKind::Default);
+}
+
+#[test]
+fn regional_varieties() {
+  let raichu = pokemon("raichu", "raichu-alola", false);
+  assert_eq!(raichu.form_name(), Some("alola"));
+  assert_eq!(raichu.form_kind(), FormKind::Regional(RegionName::Alola));
+  assert!(raichu.is_regional());
+  assert!(!raichu.is_mega());
+
+  let darmanitan = pokemon("darmanitan", "darmanitan-galar-zen", false);
+  assert_eq!(
+    darmanitan.form_kind(),
+    FormKind::Regional(RegionName::Galar)
+  );
+  let tauros = pokemon("tauros", "tauros-paldea-combat-breed", false);
+  assert_eq!(tauros.form_kind(), FormKind::Regional(RegionName::Paldea));
+  let zoroark = pokemon("zoroark", "zoroark-hisui", false);
+  assert!(zoroark.is_regional());
+}
+
+#[test]
+fn mega_varieties() {
+  let charizard = pokemon("charizard", "charizard-mega-x", false);
+  assert_eq!(charizard.form_name(), Some("mega-x"));
+  assert_eq!(charizard.form_kind(), FormKind::Mega);
+  assert!(charizard.is_mega());
+  assert!(!charizard.is_regional());
+
+  // Primal Reversion counts as a Mega Evolution.
+  let groudon = pokemon("groudon", "groudon-primal", false);
+  assert!(groudon.is_mega());
+}
+
+#[test]
+fn gigantamax_varieties() {
+  let pikachu = pokemon("pikachu", "pikachu-gmax", false);
+  assert_eq!(pikachu.form_name(), Some("gmax"));
+  assert_eq!(pikachu.form_kind(), FormKind::Gigantamax);
+  assert!(!pikachu.is_mega());
+  assert!(!pikachu.is_regional());
+}
+
+#[test]
+fn cosmetic_varieties() {
+  // Pikachu's caps are named after regions, but are not regional forms.
+  let pikachu = pokemon("pikachu", "pikachu-alola-cap", false);
+  assert_eq!(pikachu.form_name(), Some("alola-cap"));
+  assert_eq!(pikachu.form_kind(), FormKind::Other);
+  assert!(!pikachu.is_regional());
+
+  let giratina = pokemon("giratina", "giratina-origin", false);
+  assert_eq!(giratina.form_kind(), FormKind::Other);
+
+  // Varieties not named after their species can't be classified.
+  let odd = pokemon("pikachu", "raichu-alola", false);
+  assert_eq!(odd.form_name(), None);
+  assert_eq!(odd.form_kind(), FormKind::Other);
+}
+
+#[test]
+fn form_kinds() {
+  assert_eq!(form("", false).kind(), FormKind::Default);
+  assert_eq!(
+    form("alola", false).kind(),
+    FormKind::Regional(RegionName::Alola)
+  );
+  assert_eq!(
+    form("galar-standard", false).kind(),
+    FormKind::Regional(RegionName::Galar)
+  );
+  assert_eq!(form("mega-y", true).kind(), FormKind::Mega);
+  assert_eq!(form("gmax", false).kind(), FormKind::Gigantamax);
+  assert_eq!(form("alola-cap", false).kind(), FormKind::Other);
+  assert_eq!(form("sunny", false).kind(), FormKind::Other);
+
+  // The API does not flag Primal Reversions as Mega Evolutions, but its flag
+  // is trusted over the form name when set.
+  assert_eq!(form("primal", false).kind(), FormKind::Mega);
+  assert_eq!(form("", true).kind(), FormKind::Mega);
+}
+
+#[test]
+fn fixture_form() {
+  let bulbasaur: Form = common::fixture_with("api/v2/pokemon-form/1", |_| {});
+  assert_eq!(bulbasaur.form_name, "");
+  assert_eq!(bulbasaur.kind(), FormKind::Default);
+}
@@ -0,0 +1,194 @@
+//! Tests for `pkmn::calc::ivs`, which infers IVs from observed stats.
+
+mod common;
+
+use pkmn::calc::ivs::IvSolver;
+use pkmn::calc::stats::*;
+use pkmn::model::nature::Characteristic;
+use pkmn::model::StatName;
+
+/// Garchomp's base stats.
+fn garchomp() -> StatTable<u32> {
+  StatTable::from([108, 130, 95, 80, 85, 102])
+}
+
+/// Returns the stats of a Pokemon with `base` stats and `ivs` at `level`,
+/// with no effort values.
+fn stats(
+  formula: Formula,
+  level: u8,
+  base: &StatTable<u32>,
+  ivs: [u8; 6],
+) -> StatTable<u32> {
+  let spreads = StatTable::from(ivs).map(|_, &iv| Spread::new(iv, 0));
+  Calculator::new(formula, level)
+    .stats(base, &spreads)
+    .unwrap()
+}
+
+/// Returns a table of ranges from `(low, high)` pairs.
+fn ranges(
+  xs: [(u8, u8); 6],
+) -> StatTable<Option<std::ops::RangeInclusive<u8>>> {
+  StatTable::from(xs).map(|_, &(low, high)| Some(low..=high))
+}
+
+/// The "Loves to eat" characteristic: HP is highest, and is a multiple of 5.
+fn loves_to_eat() -> Characteristic {
+  common::fixture_with("api/v2/characteristic/1", |_| {})
+}
+
+#[test]
+fn observations_narrow() {
+  let ivs = [24, 12, 30, 16, 23, 5];
+  let mut solver = IvSolver::new(garchomp());
+  assert_eq!(solver.range(StatName::HitPoints), Some(0..=31));
+
+  solver
+    .observe(50, &stats(Formula::Modern, 50, &garchomp(), ivs))
+    .unwrap();
+  assert_eq!(
+    solver.ranges(),
+    ranges([(24, 25), (12, 13), (30, 31), (16, 17), (22, 23), (4, 5)])
+  );
+
+  // A level where the IVs round differently tells the pairs apart.
+  solver
+    .observe(37, &stats(Formula::Modern, 37, &garchomp(), ivs))
+    .unwrap();
+  let possible = solver.possible();
+  assert_eq!(possible.hp, [24]);
+  assert_eq!(possible.attack, [12]);
+  assert!(possible.iter().all(|(_, ivs)| !ivs.is_empty()));
+
+  // Observing the same stats again changes nothing.
+  solver
+    .observe(37, &stats(Formula::Modern, 37, &garchomp(), ivs))
+    .unwrap();
+  assert_eq!(solver.possible(), possible);
+  assert_eq!(solver.range(StatName::Accuracy), None);
+}
+
+#[test]
+fn contradictory_observations() {
+  let mut solver = IvSolver::new(garchomp());
+  let mut observed = stats(Formula::Modern, 50, &garchomp(), [31; 6]);
+  solver.observe(50, &observed).unwrap();
+
+  // No IV gives Garchomp this much HP.
+  observed.hp += 10;
+  solver.observe(50, &observed).unwrap();
+  assert_eq!(solver.range(StatName::HitPoints), None);
+  assert!(solver.possible().hp.is_empty());
+  assert_eq!(solver.range(StatName::Attack), Some(30..=31));
+}
+
+#[test]
+fn observation_errors() {
+  let mut solver = IvSolver::new(garchomp());
+  let observed = stats(Formula::Modern, 50, &garchomp(), [31; 6]);
+  assert_eq!(solver.observe(0, &observed), Err(Error::Level(0)));
+  assert_eq!(solver.observe(101, &observed), Err(Error::Level(101)));
+}
+
+#[test]
+fn characteristic_ceiling() {
+  // Every other IV is at most the highest possible HP IV.
+  let solver = IvSolver::new(garchomp()).characteristic(&loves_to_eat());
+  let possible = solver.possible();
+  assert_eq!(possible.hp, [0, 5, 10, 15, 20, 25, 30]);
+  assert_eq!(solver.range(StatName::Speed), Some(0..=30));
+
+  let mut solver = solver;
+  let ivs = [10, 8, 10, 3, 0, 9];
+  solver
+    .observe(100, &stats(Formula::Modern, 100, &garchomp(), ivs))
+    .unwrap();
+  assert_eq!(
+    solver.ranges(),
+    ranges([(10, 10), (8, 8), (10, 10), (3, 3), (0, 0), (9, 9)])
+  );
+}
+
+#[test]
+fn characteristic_floor() {
+  // At level 10, HP only narrows to 24..=31 and Attack to 30..=31.
+  let ivs = [30, 30, 0, 0, 0, 0];
+  let observed = stats(Formula::Modern, 10, &garchomp(), ivs);
+  let mut solver = IvSolver::new(garchomp());
+  solver.observe(10, &observed).unwrap();
+  assert_eq!(solver.range(StatName::HitPoints), Some(24..=31));
+  assert_eq!(solver.range(StatName::Attack), Some(30..=31));
+
+  // HP must be a multiple of 5 that is at least 30, since Attack is; that, in
+  // turn, caps Attack at 30.
+  let mut solver = IvSolver::new(garchomp()).characteristic(&loves_to_eat());
+  solver.observe(10, &observed).unwrap();
+  assert_eq!(solver.range(StatName::HitPoints), Some(30..=30));
+  assert_eq!(solver.range(StatName::Attack), Some(30..=30));
+  assert_eq!(solver.range(StatName::Defense), Some(0..=9));
+}
+
+#[test]
+fn contradictory_characteristic() {
+  // Attack is higher than any HP IV that fits, so no Attack IV is left.
+  let ivs = [5, 31, 0, 0, 0, 0];
+  let mut solver = IvSolver::new(garchomp()).characteristic(&loves_to_eat());
+  solver
+    .observe(100, &stats(Formula::Modern, 100, &garchomp(), ivs))
+    .unwrap();
+  assert_eq!(solver.range(StatName::HitPoints), Some(5..=5));
+  assert_eq!(solver.range(StatName::Attack), None);
+  assert_eq!(solver.range(StatName::Defense), Some(0..=0));
+}
+
+/// Stats for a level 25 Pokemon under [`Formula::Classic`], where each DV
+/// fits in a pair, along with its base stats.
+///
+/// The true DVs are 11 Attack, 6 Defense, 13 Speed and 4 Special, which make
+/// for an HP DV of 10.
+fn classic(sp_def_base: u32) -> (StatTable<u32>, StatTable<u32>) {
+  let base = StatTable::from([100, 100, 100, 100, sp_def_base, 100]);
+  let observed = stats(Formula::Classic, 25, &base, [0, 11, 6, 4, 4, 13]);
+  (base, observed)
+}
+
+#[test]
+fn classic_hp_dv_bits() {
+  let (base, observed) = classic(100);
+  let mut solver = IvSolver::new(base).formula(Formula::Classic);
+  solver.observe(25, &observed).unwrap();
+
+  // The HP DV is 10 or 11, so Attack and Speed must be odd and Defense even;
+  // its lowest bit is unknown, so the Special DV is too.
+  assert_eq!(
+    solver.ranges(),
+    ranges([(10, 11), (11, 11), (6, 6), (4, 5), (4, 5), (13, 13)])
+  );
+}
+
+#[test]
+fn classic_special_dv() {
+  // A different Special Defense base splits the pairs differently, so the
+  // shared Special DV is pinned down, and with it the HP DV.
+  let (base, observed) = classic(101);
+  let mut solver = IvSolver::new(base).formula(Formula::Classic);
+  solver.observe(25, &observed).unwrap();
+  assert_eq!(
+    solver.ranges(),
+    ranges([(10, 10), (11, 11), (6, 6), (4, 4), (4, 4), (13, 13)])
+  );
+}
+
+#[test]
+fn classic_contradictions() {
+  // Special Defense calls for a different Special DV than Special Attack,
+  // which leaves no HP DV either.
+  let (base, mut observed) = classic(100);
+  observed.sp_defense += 1;
+  let mut solver = IvSolver::new(base).formula(Formula::Classic);
+  solver.observe(25, &observed).unwrap();
+  assert_eq!(solver.range(StatName::SpAttack), None);
+  assert_eq!(solver.range(StatName::SpDefense), None);
+  assert_eq!(solver.range(StatName::HitPoints), None);
+}
@@ -0,0 +1,207 @@
+//! Tests for `Listing` and the iterators built on top of it.
+
+mod common;
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use pkmn::api::transport::Dump;
+use pkmn::api::transport::Transport;
+use pkmn::api::ErrorKind;
+use pkmn::api::ListingResults;
+use pkmn::api::Options;
+use pkmn::api::ReportProgress;
+use pkmn::model::Language;
+use pkmn::Api;
+
+/// Returns the names of the resources in `results`.
+fn names(results: Option<ListingResults<Language>>) -> Vec<String> {
+  results
+    .expect("expected a page")
+    .iter()
+    .map(|r| r.name().unwrap().to_string())
+    .collect()
+}
+
+/// Counts the progress reported to it.
+#[derive(Default)]
+struct Progress {
+  total: AtomicUsize,
+  completed: AtomicUsize,
+}
+
+impl Progress {
+  /// Returns the total and completed units of work reported so far.
+  fn get(&self) -> (usize, usize) {
+    let total = self.total.load(Ordering::SeqCst);
+    (total, self.completed.load(Ordering::SeqCst))
+  }
+}
+
+impl ReportProgress for Progress {
+  fn add_total(&self, n: usize) {
+    self.total.fetch_add(n, Ordering::SeqCst);
+  }
+
+  fn add_completed(&self, n: usize) {
+    self.completed.fetch_add(n, Ordering::SeqCst);
+  }
+}
+
+/// A [`Transport`] over the fixture tree that takes a while to respond.
+struct Slow(Dump);
+
+impl Transport for Slow {
+  fn get(&self, url: &str) -> Result<Vec<u8>, ErrorKind> {
+    thread::sleep(Duration::from_millis(20));
+    self.0.get(url)
+  }
+}
+
+#[test]
+fn len_is_exact_before_advancing() {
+  let api = common::api();
+  let listing = api.listing_of::<Language>(3);
+  assert_eq!(listing.len().unwrap(), 7);
+  assert!(!listing.is_empty().unwrap());
+  assert_eq!(listing.offset(), None);
+}
+
+#[test]
+fn advance_walks_every_page() {
+  let api = common::api();
+  let mut listing = api.listing_of::<Language>(3);
+  assert_eq!(
+    names(listing.advance().unwrap()),
+    ["ja-Hrkt", "roomaji", "ko"]
+  );
+  assert_eq!(names(listing.advance().unwrap()), ["zh-Hant", "fr", "de"]);
+  assert_eq!(names(listing.advance().unwrap()), ["es"]);
+  assert_eq!(listing.offset(), Some(6));
+  assert!(listing.advance().unwrap().is_none());
+}
+
+#[test]
+fn seek_and_page_at() {
+  let api = common::api();
+  let mut listing = api.listing_of::<Language>(3);
+  assert_eq!(names(listing.seek(4).unwrap()), ["fr", "de", "es"]);
+  assert_eq!(listing.offset(), Some(4));
+
+  assert_eq!(names(listing.page_at(1).unwrap()), ["zh-Hant", "fr", "de"]);
+  assert_eq!(listing.offset(), Some(3));
+
+  // Moving past the end leaves the listing where it was.
+  assert!(listing.page_at(3).unwrap().is_none());
+  assert!(listing.seek(7).unwrap().is_none());
+  assert_eq!(listing.offset(), Some(3));
+
+  assert_eq!(names(listing.advance().unwrap()), ["es"]);
+}
+
+#[test]
+fn retreat_walks_backwards_from_the_end() {
+  let api = common::api();
+  let mut listing = api.listing_of::<Language>(3);
+  assert_eq!(names(listing.retreat().unwrap()), ["es"]);
+  assert_eq!(names(listing.retreat().unwrap()), ["zh-Hant", "fr", "de"]);
+  assert_eq!(
+    names(listing.retreat().unwrap()),
+    ["ja-Hrkt", "roomaji", "ko"]
+  );
+  assert!(listing.retreat().unwrap().is_none());
+}
+
+#[test]
+fn retreat_after_unaligned_seek() {
+  let api = common::api();
+  let mut listing = api.listing_of::<Language>(3);
+  assert_eq!(names(listing.seek(2).unwrap()), ["ko", "zh-Hant", "fr"]);
+
+  // The previous page stops short of the current one.
+  assert_eq!(names(listing.retreat().unwrap()), ["ja-Hrkt", "roomaji"]);
+  assert_eq!(listing.offset(), Some(0));
+  assert!(listing.retreat().unwrap().is_none());
+
+  assert_eq!(names(listing.advance().unwrap()), ["ko", "zh-Hant", "fr"]);
+}
+
+#[test]
+fn all_yields_every_resource_in_order() {
+  let api = common::api();
+  let ids = api
+    .all::<Language>(3)
+    .map(|l| l.unwrap().id)
+    .collect::<Vec<_>>();
+  assert_eq!(ids, [1, 2, 3, 4, 5, 6, 7]);
+}
+
+#[test]
+fn all_rev_yields_every_resource_in_reverse() {
+  let api = common::api();
+  let ids = api
+    .all_rev::<Language>(3)
+    .map(|l| l.unwrap().id)
+    .collect::<Vec<_>>();
+  assert_eq!(ids, [7, 6, 5, 4, 3, 2, 1]);
+}
+
+#[test]
+fn load_all_keeps_listing_order() {
+  let api = common::api();
+  let mut listing = api.listing_of::<Language>(3);
+  listing.advance().unwrap();
+  let results = listing.advance().unwrap().unwrap();
+
+  for concurrency in [1, 3, 8] {
+    let progress = Progress::default();
+    let ids = results
+      .load_all(&api, concurrency, &progress)
+      .into_iter()
+      .map(|l| l.unwrap().id)
+      .collect::<Vec<_>>();
+    assert_eq!(ids, [4, 5, 6]);
+    assert_eq!(progress.get(), (3, 3));
+  }
+}
+
+#[test]
+fn all_parallel_yields_every_resource() {
+  for concurrency in [1, 4] {
+    let api = Arc::new(common::api());
+    let progress = Arc::new(Progress::default());
+    let mut ids = api
+      .all_parallel::<Language>(3, concurrency, Arc::clone(&progress))
+      .map(|l| l.unwrap().id)
+      .collect::<Vec<_>>();
+    ids.sort_unstable();
+    assert_eq!(ids, [1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(progress.get(), (7, 7));
+  }
+}
+
+#[test]
+fn dropping_all_parallel_stops_loading() {
+  let (logged, requests) =
+    common::Logged::new(Slow(Dump::new(common::fixtures())));
+  let api = Arc::new(Api::with_options(Options {
+    transport: Box::new(logged),
+    ..common::options()
+  }));
+  let progress = Arc::new(Progress::default());
+  let mut all = api.all_parallel::<Language>(1, 1, Arc::clone(&progress));
+  all.next().unwrap().unwrap();
+  drop(all);
+
+  // The worker finishes the resource it was loading, if any, and gives up.
+  thread::sleep(Duration::from_millis(200));
+  let made = requests.lock().unwrap().len();
+  thread::sleep(Duration::from_millis(200));
+  assert_eq!(requests.lock().unwrap().len(), made);
+  assert!(progress.get().1 <= 2, "{:?}", progress.get());
+  // Loading everything takes 14 requests: 7 pages, and 7 languages.
+  assert!(made < 14, "{}", made);
+}
@@ -0,0 +1,107 @@
+//! Tests for `AsyncApi`.
+
+#![cfg(feature = "async")]
+
+mod common;
+
+use std::sync::Arc;
+
+use futures::executor::block_on;
+use futures::StreamExt;
+use futures::TryStreamExt;
+
+use pkmn::api::transport::Dump;
+use pkmn::api::transport::Transport;
+use pkmn::api::AsyncApi;
+use pkmn::api::ErrorKind;
+use pkmn::api::Options;
+use pkmn::api::Retry;
+use pkmn::model::Language;
+
+/// Returns an [`AsyncApi`] that serves requests out of the fixture tree.
+fn api() -> AsyncApi {
+  AsyncApi::with_options(common::options())
+}
+
+/// A [`Transport`] over the fixture tree that fails every request for a URL
+/// containing a pattern.
+struct FailOn(&'static str);
+
+impl Transport for FailOn {
+  fn get(&self, url: &str) -> Result<Vec<u8>, ErrorKind> {
+    if url.contains(self.0) {
+      return Err(ErrorKind::Status(500));
+    }
+    Dump::new(common::fixtures()).get(url)
+  }
+}
+
+/// Returns an [`AsyncApi`] that serves requests with `transport`.
+fn api_with(transport: impl Transport) -> AsyncApi {
+  AsyncApi::with_options(Options {
+    transport: Box::new(transport),
+    ..common::options()
+  })
+}
+
+#[test]
+fn by_name_uses_the_transport() {
+  let api = api();
+  let by_name = block_on(api.by_name::<Language>("fr")).unwrap();
+  assert_eq!(by_name.name.to_string(), "fr");
+
+  let by_id = block_on(api.by_id::<Language>(5)).unwrap();
+  assert!(Arc::ptr_eq(&by_name, &by_id));
+}
+
+#[test]
+fn all_walks_every_page() {
+  let api = api();
+  let languages = block_on(api.all::<Language>(3).try_collect::<Vec<_>>())
+    .unwrap()
+    .iter()
+    .map(|l| l.name.to_string())
+    .collect::<Vec<_>>();
+  assert_eq!(
+    languages,
+    ["ja-Hrkt", "roomaji", "ko", "zh-Hant", "fr", "de", "es"]
+  );
+}
+
+#[test]
+fn all_skips_resources_that_fail() {
+  let api = api_with(FailOn("/language/5/"));
+  let languages = block_on(api.all::<Language>(3).collect::<Vec<_>>())
+    .into_iter()
+    .map(|l| l.map(|l| l.name.to_string()).map_err(|e| e.kind))
+    .collect::<Vec<_>>();
+  assert_eq!(languages.len(), 7);
+  assert!(matches!(languages[4], Err(ErrorKind::Status(500))));
+  assert_eq!(languages[5].as_ref().unwrap(), "de");
+  assert_eq!(languages[6].as_ref().unwrap(), "es");
+}
+
+#[test]
+fn all_stops_at_failed_pages() {
+  let api = api_with(FailOn("offset=3"));
+  let languages = block_on(api.all::<Language>(3).collect::<Vec<_>>());
+  assert_eq!(languages.len(), 4);
+  assert!(languages[..3].iter().all(Result::is_ok));
+  assert!(languages[3].is_err());
+}
+
+#[test]
+fn shares_a_cache_with_api() {
+  let api = common::api();
+  let blocking = api.by_id::<Language>(5).unwrap();
+
+  // This client has no way to fetch anything, so it must hit the cache.
+  let api = AsyncApi::with_options(Options {
+    cache: Arc::clone(api.cache()),
+    transport: Box::new(Dump::new(std::env::temp_dir().join("no-such-dump"))),
+    retry: Retry::never(),
+    ..Options::default()
+  });
+  let nonblocking = block_on(api.by_id::<Language>(5)).unwrap();
+  assert!(Arc::ptr_eq(&blocking, &nonblocking));
+}
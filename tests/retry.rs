@@ -0,0 +1,178 @@
+//! Tests for retrying failed requests.
+
+mod common;
+
+use std::time::Duration;
+
+use serde_json::json;
+use serde_json::Value;
+
+use pkmn::api::transport::Freshness;
+use pkmn::api::ErrorKind;
+use pkmn::api::Options;
+use pkmn::api::Retry;
+use pkmn::model::Language;
+use pkmn::Api;
+
+/// Returns a [`Retry`] whose backoff goes 1s, 2s, 4s, 8s, 10s, 10s...
+fn retry() -> Retry {
+  Retry {
+    max_attempts: 10,
+    initial_backoff: Duration::from_secs(1),
+    max_backoff: Duration::from_secs(10),
+    multiplier: 2.0,
+  }
+}
+
+/// Returns the fixture for the `fr` language.
+fn fr() -> Value {
+  let path = common::fixtures().join("api/v2/language/5/index.json");
+  serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap()
+}
+
+/// Returns a JSON error.
+fn json_error() -> ErrorKind {
+  serde_json::from_str::<Value>("{").unwrap_err().into()
+}
+
+#[test]
+fn backoff_grows_exponentially() {
+  let retry = retry();
+  let backoffs = (1..=7)
+    .map(|n| retry.backoff(n, &ErrorKind::ServerError(503)).unwrap())
+    .map(|d| d.as_secs())
+    .collect::<Vec<_>>();
+  assert_eq!(backoffs, [1, 2, 4, 8, 10, 10, 10]);
+
+  let constant = Retry {
+    multiplier: 0.5,
+    ..retry
+  };
+  assert_eq!(
+    constant.backoff(5, &ErrorKind::ServerError(503)),
+    Some(Duration::from_secs(1)),
+  );
+}
+
+#[test]
+fn backoff_stops_after_max_attempts() {
+  let err = ErrorKind::ServerError(503);
+  assert!(retry().backoff(9, &err).is_some());
+  assert_eq!(retry().backoff(10, &err), None);
+  assert_eq!(Retry::never().backoff(1, &err), None);
+}
+
+#[test]
+fn backoff_honors_retry_after() {
+  let limited = |secs| ErrorKind::RateLimited {
+    status: 429,
+    retry_after: Some(Duration::from_secs(secs)),
+  };
+  assert_eq!(
+    retry().backoff(1, &limited(5)),
+    Some(Duration::from_secs(5))
+  );
+  // The server's delay is a minimum, not a replacement for the backoff...
+  assert_eq!(
+    retry().backoff(4, &limited(5)),
+    Some(Duration::from_secs(8))
+  );
+  // ...nor is it capped by the maximum backoff.
+  assert_eq!(
+    retry().backoff(1, &limited(60)),
+    Some(Duration::from_secs(60))
+  );
+
+  let unspecified = ErrorKind::RateLimited {
+    status: 429,
+    retry_after: None,
+  };
+  assert_eq!(
+    retry().backoff(2, &unspecified),
+    Some(Duration::from_secs(2))
+  );
+}
+
+#[test]
+fn backoff_skips_permanent_errors() {
+  for err in [
+    ErrorKind::NotFound(404),
+    ErrorKind::Status(400),
+    ErrorKind::WrongEndpoint("pokemon"),
+    json_error(),
+  ] {
+    assert!(!err.is_transient(), "{:?}", err);
+    assert_eq!(retry().backoff(1, &err), None, "{:?}", err);
+  }
+}
+
+#[test]
+fn transient_errors_are_retried() {
+  let (fake, requests) = common::Fake::new(|n, _| match n {
+    0 | 1 => Err(ErrorKind::ServerError(503)),
+    _ => Ok(common::json(fr(), Freshness::default())),
+  });
+  let fr = common::api_with(fake, 3).by_id::<Language>(5).unwrap();
+  assert_eq!(fr.name.to_string(), "fr");
+  assert_eq!(requests.lock().unwrap().len(), 3);
+}
+
+#[test]
+fn attempts_are_counted() {
+  let (fake, requests) =
+    common::Fake::new(|_, _| Err(ErrorKind::ServerError(503)));
+  let err = common::api_with(fake, 3).by_id::<Language>(5).unwrap_err();
+  assert!(matches!(err.kind, ErrorKind::ServerError(503)), "{:?}", err);
+  assert_eq!(err.attempts, 3);
+  assert_eq!(requests.lock().unwrap().len(), 3);
+  assert_eq!(
+    err.to_string(),
+    "failed to GET `https://pokeapi.co/api/v2/language/5/` after 3 \
+     attempt(s): server error (HTTP 503)",
+  );
+
+  let (fake, requests) =
+    common::Fake::new(|_, _| Err(ErrorKind::NotFound(404)));
+  let err = common::api_with(fake, 3).by_id::<Language>(5).unwrap_err();
+  assert!(err.is_not_found());
+  assert_eq!(err.attempts, 1);
+  assert_eq!(requests.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn errors_without_requests_have_no_attempts() {
+  let (fake, requests) =
+    common::Fake::new(|_, _| Err(ErrorKind::ServerError(503)));
+  let api = common::api_with(fake, 3);
+  let err = api
+    .by_url::<Language>("https://pokeapi.co/api/v2/pokemon/5/")
+    .unwrap_err();
+  assert!(matches!(err.kind, ErrorKind::WrongEndpoint("language")));
+  assert_eq!(err.attempts, 0);
+  assert!(requests.lock().unwrap().is_empty());
+  assert!(err.to_string().starts_with("failed to load"), "{}", err);
+}
+
+#[test]
+fn bad_responses_have_no_attempts() {
+  let (fake, requests) = common::Fake::new(|_, _| {
+    Ok(common::json(json!({"id": 5}), Freshness::default()))
+  });
+  let err = common::api_with(fake, 3).by_id::<Language>(5).unwrap_err();
+  // The request succeeded, but its response could not be deserialized.
+  assert!(matches!(err.kind, ErrorKind::Json(_)), "{:?}", err);
+  assert_eq!(err.attempts, 0);
+  assert_eq!(requests.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn tiny_rate_limits_impose_no_limit() {
+  for per_second in [1e-20, 1e-300, f64::MIN_POSITIVE] {
+    let api = Api::with_options(Options {
+      requests_per_second: Some(per_second),
+      ..common::options()
+    });
+    api.by_id::<Language>(5).unwrap();
+    api.by_id::<Language>(6).unwrap();
+  }
+}
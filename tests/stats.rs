@@ -0,0 +1,131 @@
+//! Tests for `pkmn::calc::stats`, against stats of known Pokemon.
+
+mod common;
+
+use pkmn::calc::stats::*;
+use pkmn::model::StatName;
+
+/// Returns a table of spreads with the given IVs and efforts.
+fn spreads(ivs: [u8; 6], efforts: [u16; 6]) -> StatTable<Spread> {
+  let efforts = StatTable::from(efforts);
+  StatTable::from(ivs).map(|s, &iv| Spread::new(iv, *efforts.get(s).unwrap()))
+}
+
+/// A calculator with an Adamant nature (+Attack, -Special Attack).
+fn adamant(formula: Formula, level: u8) -> Calculator {
+  // Only Bold has a fixture, so swap its stats out.
+  let nature = common::fixture_with("api/v2/nature/2", |nature| {
+    nature["increased_stat"]["name"] = "attack".into();
+    nature["decreased_stat"]["name"] = "special-attack".into();
+  });
+  Calculator::new(formula, level).nature(&nature)
+}
+
+#[test]
+fn modern_garchomp() {
+  // Bulbapedia's example: a level 78 Adamant Garchomp.
+  let calc = adamant(Formula::Modern, 78);
+  let stats = calc.stats(
+    &StatTable::from([108, 130, 95, 80, 85, 102]),
+    &spreads([24, 12, 30, 16, 23, 5], [74, 190, 91, 48, 84, 23]),
+  );
+  assert_eq!(stats, Ok(StatTable::from([289, 278, 193, 135, 171, 171])));
+}
+
+#[test]
+fn modern_shedinja() {
+  let calc = Calculator::new(Formula::Modern, 100);
+  let hp = calc.stat(StatName::HitPoints, 1, Spread::new(31, 252));
+  assert_eq!(hp, Ok(1));
+}
+
+#[test]
+fn classic_tauros() {
+  // The standard Generation I Tauros, with maximum DVs and stat experience.
+  let calc = Calculator::new(Formula::Classic, 100);
+  let stats = calc.stats(
+    &StatTable::from([75, 100, 95, 70, 70, 110]),
+    &spreads([0, 15, 15, 15, 0, 15], [65535; 6]),
+  );
+  assert_eq!(stats, Ok(StatTable::from([353, 298, 288, 238, 238, 318])));
+}
+
+#[test]
+fn classic_hp_dv_from_low_bits() {
+  let dvs = spreads([0, 9, 2, 15, 0, 4], [0; 6]);
+  assert_eq!(classic_hp_dv(&dvs), 0b1001);
+}
+
+#[test]
+fn classic_ignores_nature() {
+  let calc = adamant(Formula::Classic, 100);
+  let attack = calc.stat(StatName::Attack, 100, Spread::new(15, 65535));
+  assert_eq!(attack, Ok(298));
+}
+
+#[test]
+fn lets_go_adds_avs() {
+  let calc = adamant(Formula::LetsGo, 50);
+  for &stat in &[StatName::HitPoints, StatName::Attack, StatName::SpAttack] {
+    let without = calc.stat(stat, 90, Spread::new(31, 0)).unwrap();
+    let with = calc.stat(stat, 90, Spread::new(31, 200)).unwrap();
+    assert_eq!(with, without + 200);
+  }
+
+  // Without AVs, stats match a Pokemon with no EVs.
+  let modern = adamant(Formula::Modern, 50);
+  let stat = calc.stat(StatName::Attack, 90, Spread::new(31, 0));
+  assert_eq!(stat, modern.stat(StatName::Attack, 90, Spread::new(31, 0)));
+}
+
+#[test]
+fn rejects_invalid_inputs() {
+  let calc = Calculator::new(Formula::Modern, 50);
+  assert_eq!(
+    Calculator::new(Formula::Modern, 101).stat(
+      StatName::Speed,
+      100,
+      Spread::default()
+    ),
+    Err(Error::Level(101)),
+  );
+  assert_eq!(
+    calc.stat(StatName::Accuracy, 100, Spread::default()),
+    Err(Error::BattleOnly(StatName::Accuracy)),
+  );
+  assert_eq!(
+    calc.stat(StatName::Speed, 100, Spread::new(32, 0)),
+    Err(Error::Iv {
+      stat: StatName::Speed,
+      value: 32,
+      max: 31
+    }),
+  );
+  assert_eq!(
+    calc.stat(StatName::Speed, 100, Spread::new(0, 256)),
+    Err(Error::Effort {
+      stat: StatName::Speed,
+      value: 256,
+      max: 255
+    }),
+  );
+  assert_eq!(
+    calc.stats(
+      &StatTable::from([100; 6]),
+      &spreads([0; 6], [252, 252, 8, 0, 0, 0])
+    ),
+    Err(Error::TotalEvs(512)),
+  );
+  assert_eq!(
+    Calculator::new(Formula::Classic, 50).stat(
+      StatName::Speed,
+      100,
+      Spread::new(16, 0)
+    ),
+    Err(Error::Iv {
+      stat: StatName::Speed,
+      value: 16,
+      max: 15
+    }),
+  );
+}
@@ -0,0 +1,134 @@
+//! Tests for looking up `Localized` text by language and version.
+
+mod common;
+
+use pkmn::model::Ability;
+use pkmn::model::LanguageName;
+use pkmn::model::LanguagePreference;
+use pkmn::model::VersionGroupName;
+use serde_json::json;
+
+/// Returns Overgrow, with its flavor text replaced by `flavor_text`, given as
+/// `(language, version group name, version group ID, text)`.
+fn overgrow(flavor_text: &[(&str, &str, u32, &str)]) -> Ability {
+  common::fixture_with("api/v2/ability/65", |ability| {
+    ability["flavor_text_entries"] = flavor_text
+      .iter()
+      .map(|&(lang, vg, id, text)| {
+        json!({
+          "flavor_text": text,
+          "language": { "name": lang, "url": "/api/v2/language/0/" },
+          "version_group": {
+            "name": vg,
+            "url": format!("/api/v2/version-group/{}/", id),
+          },
+        })
+      })
+      .collect();
+  })
+}
+
+#[test]
+fn language_fallback() {
+  let ability = overgrow(&[]);
+  let names = &ability.localized_names;
+  assert_eq!(names.get(LanguageName::English), Some("Overgrow"));
+  assert_eq!(names.get(LanguageName::French), Some("Engrais"));
+  assert_eq!(names.get(LanguageName::German), None);
+
+  // The first language in the chain that is present wins.
+  let prefs = LanguagePreference::from(LanguageName::German)
+    .then(LanguageName::French)
+    .then(LanguageName::English);
+  assert_eq!(names.get(&prefs), Some("Engrais"));
+  assert_eq!(names.get(prefs), Some("Engrais"));
+  assert_eq!(
+    names.get([LanguageName::JapaneseHiragana, LanguageName::English]),
+    Some("Overgrow")
+  );
+  assert_eq!(
+    names.get([LanguageName::German, LanguageName::Korean]),
+    None
+  );
+  assert_eq!(names.get(LanguagePreference::default()), Some("Overgrow"));
+}
+
+#[test]
+fn get_returns_the_first_match() {
+  let ability = overgrow(&[
+    ("en", "ruby-sapphire", 5, "Ruby"),
+    ("fr", "x-y", 15, "X"),
+    ("en", "x-y", 15, "Y"),
+  ]);
+  let text = &ability.flavor_text;
+  assert_eq!(text.get(LanguageName::English), Some("Ruby"));
+  assert_eq!(text.get(LanguageName::French), Some("X"));
+}
+
+#[test]
+fn get_in_versions() {
+  let ability = overgrow(&[
+    ("en", "ruby-sapphire", 5, "Ruby"),
+    ("fr", "x-y", 15, "X"),
+    ("en", "x-y", 15, "Y"),
+  ]);
+  let text = &ability.flavor_text;
+  assert_eq!(
+    text.get_in(VersionGroupName::XY, LanguageName::English),
+    Some("Y")
+  );
+  assert_eq!(
+    text.get_in(
+      VersionGroupName::RubySapphire,
+      [LanguageName::French, LanguageName::English]
+    ),
+    Some("Ruby")
+  );
+  assert_eq!(
+    text.get_in(VersionGroupName::SunMoon, LanguageName::English),
+    None
+  );
+}
+
+#[test]
+fn get_in_or_latest_falls_back_by_id() {
+  // PokeAPI does not always list versions in order.
+  let ability = overgrow(&[
+    ("en", "sun-moon", 17, "Sun"),
+    ("en", "ruby-sapphire", 5, "Ruby"),
+    ("fr", "ruby-sapphire", 5, "Rubis"),
+    ("en", "x-y", 15, "Y"),
+  ]);
+  let text = &ability.flavor_text;
+
+  // An exact match is preferred.
+  assert_eq!(
+    text.get_in_or_latest(VersionGroupName::XY, LanguageName::English),
+    Some("Y")
+  );
+  // Otherwise, the version with the highest ID wins, not the last listed.
+  assert_eq!(
+    text.get_in_or_latest(VersionGroupName::Platinum, LanguageName::English),
+    Some("Sun")
+  );
+  // Language preference beats recency.
+  assert_eq!(
+    text.get_in_or_latest(
+      VersionGroupName::Platinum,
+      [LanguageName::French, LanguageName::English]
+    ),
+    Some("Rubis")
+  );
+  // An exact match in a less preferred language beats falling back.
+  assert_eq!(
+    text.get_in_or_latest(
+      VersionGroupName::XY,
+      [LanguageName::French, LanguageName::English]
+    ),
+    Some("Y")
+  );
+  assert_eq!(
+    text.get_in_or_latest(VersionGroupName::XY, LanguageName::German),
+    None
+  );
+}
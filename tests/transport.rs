@@ -0,0 +1,286 @@
+//! Tests for the transports in `pkmn::api::transport`.
+
+mod common;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::json;
+use serde_json::Value;
+
+use reqwest::header;
+use reqwest::header::HeaderMap;
+use reqwest::header::HeaderValue;
+use reqwest::StatusCode;
+
+use pkmn::api::transport::check_status;
+use pkmn::api::transport::Conditional;
+use pkmn::api::transport::Directory;
+use pkmn::api::transport::Dump;
+use pkmn::api::transport::Freshness;
+use pkmn::api::transport::Transport;
+use pkmn::api::Cache;
+use pkmn::api::ErrorKind;
+use pkmn::api::Options;
+use pkmn::model::Language;
+use pkmn::Api;
+
+/// Returns a [`Directory`] over the `directory` fixture tree.
+fn directory() -> Directory {
+  Directory::new(common::fixture("directory"))
+}
+
+/// Returns the path `path_for` should produce for `rel`.
+fn expected(rel: &str) -> PathBuf {
+  common::fixture("directory").join(rel)
+}
+
+#[test]
+fn path_for_resource() {
+  let dir = directory();
+  let path = expected("pokeapi.co/api/v2/language/5.json");
+  assert_eq!(dir.path_for("https://pokeapi.co/api/v2/language/5/"), path);
+  assert_eq!(dir.path_for("https://pokeapi.co/api/v2/language/5"), path);
+  assert_eq!(dir.path_for("http://pokeapi.co//api/v2/language/5//"), path);
+  assert_eq!(dir.path_for("pokeapi.co/api/v2/language/5/"), path);
+}
+
+#[test]
+fn path_for_query() {
+  let dir = directory();
+  assert_eq!(
+    dir.path_for("https://pokeapi.co/api/v2/pokemon/?limit=64"),
+    expected("pokeapi.co/api/v2/pokemon@limit_64.json"),
+  );
+  assert_eq!(
+    dir.path_for("https://pokeapi.co/api/v2/pokemon?offset=64&limit=64"),
+    expected("pokeapi.co/api/v2/pokemon@offset_64_limit_64.json"),
+  );
+}
+
+#[test]
+fn path_for_keeps_extensions() {
+  assert_eq!(
+    directory().path_for("https://example.com/sprites/pokemon/1.png"),
+    expected("example.com/sprites/pokemon/1.png"),
+  );
+}
+
+#[test]
+fn directory_serves_files() {
+  let dir = directory();
+  let body = dir.get("https://pokeapi.co/api/v2/language/5/").unwrap();
+  let value: Value = serde_json::from_slice(&body).unwrap();
+  assert_eq!(value["name"], "fr");
+
+  let err = dir
+    .get("https://pokeapi.co/api/v2/language/6/")
+    .unwrap_err();
+  assert!(matches!(err, ErrorKind::NotFound(404)), "{:?}", err);
+}
+
+#[test]
+fn api_over_directory() {
+  let api = Api::with_options(Options {
+    transport: Box::new(directory()),
+    ..common::options()
+  });
+  let fr = api.by_id::<Language>(5).unwrap();
+  assert_eq!(fr.name.to_string(), "fr");
+
+  let mut listing = api.listing_of::<Language>(2);
+  let names = listing
+    .advance()
+    .unwrap()
+    .unwrap()
+    .iter()
+    .map(|r| r.name().unwrap().to_string())
+    .collect::<Vec<_>>();
+  assert_eq!(names, ["ja-Hrkt", "roomaji"]);
+  assert!(listing.advance().is_err());
+}
+
+/// Fetches `url` from a [`Dump`] of the fixture tree, as JSON.
+fn dump(url: &str) -> Result<Value, ErrorKind> {
+  let body = Dump::new(common::fixtures()).get(url)?;
+  Ok(serde_json::from_slice(&body).unwrap())
+}
+
+#[test]
+fn dump_resolves_names() {
+  let by_id = dump("https://pokeapi.co/api/v2/language/5/").unwrap();
+  let by_name = dump("https://pokeapi.co/api/v2/language/fr/").unwrap();
+  assert_eq!(by_id, by_name);
+  assert_eq!(by_name["id"], 5);
+
+  let err = dump("https://pokeapi.co/api/v2/language/xx/").unwrap_err();
+  assert!(matches!(err, ErrorKind::NotFound(404)), "{:?}", err);
+}
+
+#[test]
+fn dump_pages_listings() {
+  let first = dump("https://pokeapi.co/api/v2/language?limit=3").unwrap();
+  assert_eq!(first["count"], 7);
+  assert_eq!(
+    first["next"],
+    "https://pokeapi.co/api/v2/language/?offset=3&limit=3"
+  );
+  assert_eq!(first["previous"], Value::Null);
+  assert_eq!(first["results"].as_array().unwrap().len(), 3);
+  assert_eq!(first["results"][0]["name"], "ja-Hrkt");
+
+  let last =
+    dump("https://pokeapi.co/api/v2/language?offset=6&limit=3").unwrap();
+  assert_eq!(last["next"], Value::Null);
+  assert_eq!(
+    last["previous"],
+    "https://pokeapi.co/api/v2/language/?offset=3&limit=3"
+  );
+  assert_eq!(
+    last["results"],
+    json!([{"name": "es", "url": "https://pokeapi.co/api/v2/language/7/"}])
+  );
+
+  let past_end =
+    dump("https://pokeapi.co/api/v2/language?offset=10&limit=3").unwrap();
+  assert_eq!(past_end["results"], json!([]));
+
+  let default = dump("https://pokeapi.co/api/v2/language/").unwrap();
+  assert_eq!(default["results"].as_array().unwrap().len(), 7);
+  assert_eq!(default["next"], Value::Null);
+}
+
+#[test]
+fn dump_rewrites_urls() {
+  let fr = dump("https://pokeapi.co/api/v2/language/5/").unwrap();
+  assert_eq!(
+    fr["names"][0]["language"]["url"],
+    "https://pokeapi.co/api/v2/language/5/"
+  );
+
+  let dump = Dump::with_origin(common::fixtures(), "http://localhost:8000/");
+  assert_eq!(dump.origin(), "http://localhost:8000");
+  let body = dump
+    .get("http://localhost:8000/api/v2/language/fr/")
+    .unwrap();
+  let fr: Value = serde_json::from_slice(&body).unwrap();
+  assert_eq!(
+    fr["names"][0]["language"]["url"],
+    "http://localhost:8000/api/v2/language/5/"
+  );
+
+  let page = dump.get("http://localhost:8000/api/v2/language?limit=1");
+  let page: Value = serde_json::from_slice(&page.unwrap()).unwrap();
+  assert_eq!(
+    page["results"][0]["url"],
+    "http://localhost:8000/api/v2/language/1/"
+  );
+  assert_eq!(
+    page["next"],
+    "http://localhost:8000/api/v2/language/?offset=1&limit=1"
+  );
+}
+
+#[test]
+fn dump_rejects_other_paths() {
+  for url in [
+    "https://pokeapi.co/",
+    "https://pokeapi.co/api/v2/",
+    "https://pokeapi.co/api/v1/language/5/",
+  ] {
+    let err = dump(url).unwrap_err();
+    assert!(
+      matches!(err, ErrorKind::NotFound(404)),
+      "{}: {:?}",
+      url,
+      err
+    );
+  }
+}
+
+/// Runs [`check_status()`] on a response with the given status and headers.
+fn status(code: u16, headers: &[(header::HeaderName, &str)]) -> ErrorKind {
+  let mut map = HeaderMap::new();
+  for (name, value) in headers {
+    map.insert(name, HeaderValue::from_str(value).unwrap());
+  }
+  check_status(StatusCode::from_u16(code).unwrap(), &map).unwrap_err()
+}
+
+#[test]
+fn check_status_classifies_errors() {
+  let ok = HeaderMap::new();
+  assert!(check_status(StatusCode::OK, &ok).is_ok());
+  assert!(check_status(StatusCode::NO_CONTENT, &ok).is_ok());
+
+  assert!(matches!(status(404, &[]), ErrorKind::NotFound(404)));
+  assert!(matches!(status(410, &[]), ErrorKind::NotFound(410)));
+  assert!(matches!(status(500, &[]), ErrorKind::ServerError(500)));
+  assert!(matches!(status(503, &[]), ErrorKind::ServerError(503)));
+  assert!(matches!(status(400, &[]), ErrorKind::Status(400)));
+  assert!(matches!(status(304, &[]), ErrorKind::Status(304)));
+}
+
+#[test]
+fn check_status_reads_retry_after() {
+  let err = status(429, &[(header::RETRY_AFTER, " 120 ")]);
+  assert!(
+    matches!(
+      err,
+      ErrorKind::RateLimited {
+        status: 429,
+        retry_after: Some(d),
+      } if d == Duration::from_secs(120)
+    ),
+    "{:?}",
+    err
+  );
+
+  // HTTP dates are not supported, and are treated as if there were no
+  // header at all.
+  for headers in [
+    &[][..],
+    &[(header::RETRY_AFTER, "Wed, 21 Oct 2015 07:28:00 GMT")][..],
+  ] {
+    let err = status(429, headers);
+    assert!(
+      matches!(
+        err,
+        ErrorKind::RateLimited {
+          status: 429,
+          retry_after: None,
+        }
+      ),
+      "{:?}",
+      err
+    );
+  }
+}
+
+#[test]
+fn not_found_is_not_cached() {
+  let dir = common::TempDir::new("not-found-is-not-cached");
+  let (fake, requests) = common::Fake::new(|n, _| match n {
+    0 => Err(ErrorKind::NotFound(404)),
+    _ => {
+      let path = common::fixtures().join("api/v2/language/5/index.json");
+      let body = std::fs::read(path).unwrap();
+      Ok(Conditional::Modified(body, Freshness::default()))
+    }
+  });
+  let api = Api::with_options(Options {
+    cache: Arc::new(Cache::with_dir(0, dir.path().to_path_buf())),
+    transport: Box::new(fake),
+    ..common::options()
+  });
+
+  assert!(api.by_id::<Language>(5).unwrap_err().is_not_found());
+  assert_eq!(api.cache().entries().count(), 0);
+  assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+
+  // The next request goes to the transport again, rather than to the cache.
+  assert_eq!(api.by_id::<Language>(5).unwrap().name.to_string(), "fr");
+  assert_eq!(requests.lock().unwrap().len(), 2);
+  assert_eq!(api.cache().entries().count(), 1);
+}
@@ -0,0 +1,251 @@
+//! Tests for `pkmn::calc::TypeChart`, against a subset of the real chart.
+
+mod common;
+
+use std::sync::Arc;
+
+use pkmn::calc::Matchups;
+use pkmn::calc::TypeChart;
+use pkmn::model::GenerationName;
+use pkmn::model::Type;
+use pkmn::model::TypeName::*;
+use serde_json::json;
+use serde_json::Value;
+
+/// Returns links to each of `names`.
+fn links(names: &[&str]) -> Value {
+  names
+    .iter()
+    .map(|name| json!({ "name": name, "url": "/api/v2/type/0/" }))
+    .collect()
+}
+
+/// Returns damage relations with the given attacking multipliers.
+fn relations(double: &[&str], half: &[&str], none: &[&str]) -> Value {
+  json!({
+    "double_damage_to": links(double),
+    "half_damage_to": links(half),
+    "no_damage_to": links(none),
+    "double_damage_from": [],
+    "half_damage_from": [],
+    "no_damage_from": [],
+  })
+}
+
+/// Returns a type, built on top of the Grass fixture.
+fn ty(
+  id: u32,
+  name: &str,
+  generation: &str,
+  relations: Value,
+  past: &[(&str, Value)],
+) -> Type {
+  common::fixture_with("api/v2/type/12", |ty| {
+    ty["id"] = id.into();
+    ty["name"] = name.into();
+    ty["generation"]["name"] = generation.into();
+    ty["damage_relations"] = relations;
+    ty["past_damage_relations"] = past
+      .iter()
+      .map(|(generation, relations)| {
+        json!({
+          "generation": { "name": generation, "url": "/api/v2/generation/0/" },
+          "damage_relations": relations,
+        })
+      })
+      .collect();
+  })
+}
+
+/// Returns ten types from the real chart, with their relations to one another
+/// through the generations.
+fn types() -> Vec<Type> {
+  let gen1 = "generation-i";
+  let gen2 = "generation-ii";
+  let gen5 = "generation-v";
+  let gen6 = "generation-vi";
+  vec![
+    // Listed out of order, to check that the chart sorts by ID.
+    ty(
+      18,
+      "fairy",
+      gen6,
+      relations(&["dragon", "dark"], &["steel"], &[]),
+      &[],
+    ),
+    ty(
+      1,
+      "normal",
+      gen1,
+      relations(&[], &["steel"], &["ghost"]),
+      &[],
+    ),
+    ty(3, "flying", gen1, relations(&[], &["steel"], &[]), &[]),
+    ty(
+      8,
+      "ghost",
+      gen1,
+      relations(&["ghost", "psychic"], &["dark"], &["normal"]),
+      &[
+        (gen1, relations(&["ghost"], &[], &["normal", "psychic"])),
+        (
+          gen5,
+          relations(&["ghost", "psychic"], &["dark", "steel"], &["normal"]),
+        ),
+      ],
+    ),
+    ty(
+      9,
+      "steel",
+      gen2,
+      relations(&["ice", "fairy"], &["water", "steel"], &[]),
+      &[],
+    ),
+    ty(
+      11,
+      "water",
+      gen1,
+      relations(&[], &["water", "dragon"], &[]),
+      &[],
+    ),
+    ty(
+      14,
+      "psychic",
+      gen1,
+      relations(&[], &["psychic", "steel"], &["dark"]),
+      &[],
+    ),
+    ty(
+      15,
+      "ice",
+      gen1,
+      relations(&["flying", "dragon"], &["water", "ice", "steel"], &[]),
+      &[],
+    ),
+    ty(
+      16,
+      "dragon",
+      gen1,
+      relations(&["dragon"], &["steel"], &["fairy"]),
+      &[],
+    ),
+    ty(
+      17,
+      "dark",
+      gen2,
+      relations(&["psychic", "ghost"], &["dark", "fairy"], &[]),
+      &[(
+        gen5,
+        relations(&["psychic", "ghost"], &["dark", "steel"], &[]),
+      )],
+    ),
+  ]
+}
+
+#[test]
+fn current_chart() {
+  // Charts can be built from slices of types, not just of `Arc`s.
+  let types = types();
+  let chart = TypeChart::new(types.as_slice());
+  assert_eq!(
+    chart.types(),
+    [Normal, Flying, Ghost, Steel, Water, Psychic, Ice, Dragon, Dark, Fairy]
+  );
+
+  assert_eq!(chart.effectiveness(Ghost, Psychic), 2.0);
+  assert_eq!(chart.effectiveness(Ghost, Steel), 1.0);
+  assert_eq!(chart.effectiveness(Ghost, Normal), 0.0);
+  assert_eq!(chart.effectiveness(Dragon, Fairy), 0.0);
+  assert_eq!(chart.effectiveness(Fairy, Dragon), 2.0);
+  assert_eq!(chart.effectiveness(Ice, Water), 0.5);
+  assert_eq!(chart.effectiveness(Normal, Normal), 1.0);
+
+  // Types outside the chart are neutral.
+  assert_eq!(chart.effectiveness(Fire, Ice), 1.0);
+  assert_eq!(chart.effectiveness(Ice, Grass), 1.0);
+}
+
+#[test]
+fn charts_from_arcs() {
+  let types = types().into_iter().map(Arc::new).collect::<Vec<_>>();
+  let chart = TypeChart::new(&types);
+  assert_eq!(chart.effectiveness(Dragon, Fairy), 0.0);
+  let chart = TypeChart::for_generation(types, GenerationName::I);
+  assert_eq!(chart.effectiveness(Ghost, Psychic), 0.0);
+}
+
+#[test]
+fn dual_types() {
+  let chart = TypeChart::new(types());
+
+  // Dragonite is doubly weak to Ice.
+  assert_eq!(chart.multiplier(Ice, &[Dragon, Flying]), 4.0);
+  // Lapras doubly resists Ice.
+  assert_eq!(chart.multiplier(Ice, &[Water, Ice]), 0.25);
+  // Immunities override weaknesses.
+  assert_eq!(chart.multiplier(Psychic, &[Dark, Ghost]), 0.0);
+  assert_eq!(chart.multiplier(Ghost, &[Normal, Psychic]), 0.0);
+  // Weaknesses and resistances cancel out.
+  assert_eq!(chart.multiplier(Fairy, &[Dragon, Steel]), 1.0);
+
+  assert_eq!(
+    chart.matchups(&[Dragon, Flying]),
+    Matchups {
+      weaknesses: vec![(Ice, 4.0), (Dragon, 2.0), (Fairy, 2.0)],
+      resistances: vec![(Water, 0.5)],
+      immunities: vec![],
+    }
+  );
+  assert_eq!(
+    chart.matchups(&[Ghost, Dark]),
+    Matchups {
+      weaknesses: vec![(Fairy, 2.0)],
+      resistances: vec![],
+      immunities: vec![Normal, Psychic],
+    }
+  );
+}
+
+#[test]
+fn generation_i_chart() {
+  let chart = TypeChart::for_generation(types(), GenerationName::I);
+  assert_eq!(
+    chart.types(),
+    [Normal, Flying, Ghost, Water, Psychic, Ice, Dragon]
+  );
+
+  // Ghost moves famously had no effect on Psychic Pokemon.
+  assert_eq!(chart.effectiveness(Ghost, Psychic), 0.0);
+  assert_eq!(chart.effectiveness(Ghost, Ghost), 2.0);
+  assert_eq!(chart.effectiveness(Ghost, Normal), 0.0);
+
+  // Steel, Dark and Fairy did not exist yet.
+  assert_eq!(chart.effectiveness(Dark, Psychic), 1.0);
+  assert_eq!(chart.effectiveness(Ice, Steel), 1.0);
+  assert_eq!(chart.multiplier(Ice, &[Dragon, Flying]), 4.0);
+}
+
+#[test]
+fn pre_fairy_chart() {
+  for generation in [GenerationName::II, GenerationName::V] {
+    let chart = TypeChart::for_generation(types(), generation);
+    assert_eq!(
+      chart.types(),
+      [Normal, Flying, Ghost, Steel, Water, Psychic, Ice, Dragon, Dark]
+    );
+
+    assert_eq!(chart.effectiveness(Ghost, Psychic), 2.0);
+    // Steel resisted Ghost and Dark until Generation VI.
+    assert_eq!(chart.effectiveness(Ghost, Steel), 0.5);
+    assert_eq!(chart.effectiveness(Dark, Steel), 0.5);
+    assert_eq!(chart.multiplier(Dark, &[Ghost, Steel]), 1.0);
+
+    // Fairy did not exist, so nothing was immune to Dragon.
+    assert_eq!(chart.effectiveness(Dragon, Fairy), 1.0);
+  }
+
+  let chart = TypeChart::for_generation(types(), GenerationName::VI);
+  assert_eq!(chart.types().len(), 10);
+  assert_eq!(chart.effectiveness(Ghost, Steel), 1.0);
+  assert_eq!(chart.effectiveness(Dragon, Fairy), 0.0);
+}